@@ -1,206 +1,2646 @@
+use crate::error::CreateError;
+use crate::gdextension::{quote, unquote, GdExtensionFile};
+use crate::i18n::{set_language, tr, tr_fmt, Language};
+use crate::log::{Log, LogEntry, LogLevel};
+use crate::metadata::ProjectMetadata;
+use crate::settings::{AppSettings, Preset, RecentProject, Theme};
+use crate::storage::write_atomic;
+use crate::template_source::TemplateSource;
+use crate::update_check::{check_for_update, Release};
 use crate::utils::*;
 use eframe::egui::{self};
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use std::thread;
+use std::time::{Duration, Instant, SystemTime};
+use tokio::sync::{mpsc, oneshot};
 
-const DEFAULT_GODOT_VERSION: &str = "4.2";
 const PROJECT_NAME_HINT: &str = "Logs will appear here...";
-const LOG_MAX_HEIGHT: f32 = 300.0;
+// The log takes the height left below the form, but never less than this
+const LOG_MIN_HEIGHT: f32 = 120.0;
+// Room kept under the log for its buttons
+const LOG_FOOTER_HEIGHT: f32 = 32.0;
 const LOG_TEXT_WIDTH: f32 = 470.0;
+// Wide enough for a target with its library path field, a wider window fits more columns
+const TARGET_COLUMN_WIDTH: f32 = 320.0;
+const LOG_FILE: &str = "gen_gdext.log";
+const LIBRARY_PATH_INDENT: f32 = 24.0;
+const PRESET_NAME_WIDTH: f32 = 140.0;
+const PROFILE_NAME_WIDTH: f32 = 100.0;
+const BUILD_PROGRESS_WIDTH: f32 = 200.0;
+const PREVIEW_TREE_HEIGHT: f32 = 120.0;
+const PREVIEW_CONTENT_HEIGHT: f32 = 220.0;
+const TEMPLATE_EDITOR_HEIGHT: f32 = 320.0;
+const RELEASE_NOTES_HEIGHT: f32 = 160.0;
+// How often a local templates.yaml is checked for changes made outside the window
+const TEMPLATE_WATCH_INTERVAL: Duration = Duration::from_secs(1);
+// How long quitting waits for a cancelled task to kill its processes and roll back
+const SHUTDOWN_TIMEOUT: Duration = Duration::from_secs(10);
+
+// A question from a running task, answered through `reply`
+struct Prompt {
+    title: &'static str,
+    message: String,
+    accept: &'static str,
+    reject: &'static str,
+    reply: oneshot::Sender<bool>,
+}
+
+// What a running task sends back to the window, `Finished` is always the last message
+enum TaskEvent {
+    Prompt(Prompt),
+    Finished(CreationState),
+}
+
+// Lets a running task ask the user, each question blocks the task until the window answers it
+struct Asker(mpsc::UnboundedSender<TaskEvent>);
+
+impl Asker {
+    // A dropped prompt counts as a rejection
+    fn ask(&self, title: &'static str, message: String, accept: &'static str, reject: &'static str) -> bool {
+        let (reply, answer) = oneshot::channel();
+        let prompt = Prompt {
+            title,
+            message,
+            accept,
+            reject,
+            reply,
+        };
+        if self.0.send(TaskEvent::Prompt(prompt)).is_err() {
+            return false;
+        }
+        answer.blocking_recv().unwrap_or(false)
+    }
+}
+
+// A .gdextension opened for editing, shown with the creation widgets and written back into the parsed file
+#[derive(Default)]
+struct GdExtensionEditor {
+    path: String,
+    // The file that was opened, None until one is
+    file: Option<(String, GdExtensionFile)>,
+    entry_symbol: String,
+    compatibility_minimum: String,
+    compatibility_maximum: String,
+    reloadable: bool,
+    // KNOWN_TARGETS and whatever other feature tags the file lists, with their library paths
+    libraries: Vec<(String, bool, String)>,
+}
+
+impl GdExtensionEditor {
+    fn open(path: &str) -> Result<Self, String> {
+        let content = fs::read_to_string(path).map_err(|err| tr_fmt("Failed to read {}: {}", &[&path, &err]))?;
+        let file = GdExtensionFile::parse(&content).map_err(|err| tr_fmt("{} is not a valid .gdextension. {}", &[&path, &err]))?;
+        let configuration = |key| file.get("configuration", key).map(unquote).unwrap_or_default();
+        let mut libraries: Vec<(String, bool, String)> = KNOWN_TARGETS.iter().map(|target| (target.to_string(), false, String::new())).collect();
+        for (target, library) in file.entries("libraries") {
+            match libraries.iter_mut().find(|(known, _, _)| known == target) {
+                Some((_, is_selected, path)) => {
+                    *is_selected = true;
+                    *path = unquote(library);
+                }
+                None => libraries.push((target.to_string(), true, unquote(library))),
+            }
+        }
+        Ok(Self {
+            path: path.to_string(),
+            entry_symbol: configuration("entry_symbol"),
+            compatibility_minimum: configuration("compatibility_minimum"),
+            compatibility_maximum: configuration("compatibility_maximum"),
+            reloadable: configuration("reloadable") == "true",
+            libraries,
+            file: Some((path.to_string(), file)),
+        })
+    }
+
+    fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if self.entry_symbol.trim().is_empty() {
+            problems.push(tr("The entry symbol can't be empty.").to_string());
+        }
+        if let Err(err) = self.compatibility_minimum.parse::<GodotVersion>() {
+            problems.push(tr_fmt("Min Godot version: {}", &[&err]));
+        }
+        if let Err(err) = Some(self.compatibility_maximum.trim())
+            .filter(|maximum| !maximum.is_empty())
+            .map(str::parse::<GodotVersion>)
+            .transpose()
+        {
+            problems.push(tr_fmt("Max Godot version: {}", &[&err]));
+        }
+        problems
+    }
+
+    // Only what changed is set, so untouched entries keep their formatting. A ticked target with an empty path gets its default
+    fn save(&mut self, default_path: &dyn Fn(&str) -> Option<String>) -> Result<String, String> {
+        let Some((path, file)) = &mut self.file else {
+            return Err(tr("No .gdextension is open.").to_string());
+        };
+        if (file.get("configuration", "reloadable") == Some("true")) != self.reloadable {
+            file.set("configuration", "reloadable", self.reloadable.to_string());
+        }
+        let mut set = |section: &str, key: &str, value: Option<String>, raw: fn(&str) -> String| {
+            let current = file.get(section, key).map(unquote);
+            match value {
+                Some(value) if current.as_ref() != Some(&value) => file.set(section, key, raw(&value)),
+                Some(_) => {}
+                None => file.remove(section, key),
+            }
+        };
+        set("configuration", "entry_symbol", Some(self.entry_symbol.trim().to_string()), quote);
+        set(
+            "configuration",
+            "compatibility_minimum",
+            Some(self.compatibility_minimum.trim().to_string()),
+            str::to_string,
+        );
+        let maximum = Some(self.compatibility_maximum.trim().to_string()).filter(|maximum| !maximum.is_empty());
+        set("configuration", "compatibility_maximum", maximum, str::to_string);
+        for (target, is_selected, library) in &self.libraries {
+            let library = match library.trim() {
+                "" => default_path(target),
+                library => Some(library.to_string()),
+            };
+            set("libraries", target, library.filter(|_| *is_selected), quote);
+        }
+        write_atomic(Path::new(path), &file.to_string()).map_err(|err| tr_fmt("Failed to write {}: {}", &[&path, &err]))?;
+        Ok(path.clone())
+    }
+}
+
+// A clickable action next to a quick-start step
+enum QuickStartAction {
+    Copy(String),
+    Build,
+    OpenInGodot,
+    OpenInEditor,
+}
+
+struct QuickStartStep {
+    text: String,
+    actions: Vec<QuickStartAction>,
+}
+
+enum RecentAction {
+    OpenFolder,
+    OpenInGodot,
+    Recreate,
+    Forget,
+}
+
+#[derive(Clone, PartialEq)]
+enum CreationState {
+    Idle,
+    Running,
+    Done(String),
+    Failed(String),
+}
 
 pub struct App {
     project_name: String,
-    log: Arc<Mutex<String>>,
-    is_creating: bool,
+    log: Arc<Mutex<Log>>,
+    // The least important level the log shows
+    log_level: LogLevel,
+    // Set while the save dialog is open
+    log_save_path: Option<String>,
+    state: CreationState,
+    task_events: Option<mpsc::UnboundedReceiver<TaskEvent>>,
+    cancel: Arc<AtomicBool>,
     templates: Option<ProjectTemplates>,
+    // One shell command per line
+    post_create_hooks: String,
+    template_hooks: bool,
+    template_source: String,
+    // What the current templates were loaded from, the field above may be mid-edit
+    loaded_template_source: String,
+    template_error: Option<String>,
+    template_receiver: Option<oneshot::Receiver<Result<ProjectTemplates, String>>>,
+    host_triple: Option<String>,
+    host_receiver: Option<oneshot::Receiver<Option<String>>>,
+    prerequisites: Vec<PrerequisiteCheck>,
+    prerequisite_receiver: Option<oneshot::Receiver<Vec<PrerequisiteCheck>>>,
     godot_version: String,
     reloadable: bool,
+    entry_symbol: String,
+    max_godot_version: String,
     targets: Vec<(String, bool)>,
+    library_paths: BTreeMap<String, String>,
+    target_profiles: BTreeMap<String, String>,
+    target_dir: String,
+    extension_crates: String,
+    template_variables: Vec<(String, String)>,
+    godot_features: Vec<(String, bool)>,
+    dependencies: Vec<(String, bool)>,
     autofocus_input: bool,
     precompile_lib: bool,
+    sign_libraries: bool,
+    minisign_key: String,
+    existing_project: ExistingProject,
+    confirm_project_overwrite: bool,
+    confirm_exit: bool,
+    // Set once quitting was confirmed, the window closes when the cancelled task is done
+    shutdown_started: Option<Instant>,
+    editor_plugin: bool,
+    minimal_size: bool,
+    release_profile: ReleaseProfile,
+    double_precision: bool,
+    prelude_module: bool,
+    code_style: bool,
+    itest: bool,
+    class_icons: bool,
+    git_init: bool,
+    git_commit_message: String,
+    license: License,
+    author: String,
+    library_layout: LibraryLayout,
+    readme: bool,
+    github_workflow: bool,
+    export_presets: bool,
+    task_runner: TaskRunner,
+    vscode: bool,
+    env_vars: Vec<(String, String)>,
+    cargo: String,
+    cargo_flags: String,
+    godot: String,
+    open_in_godot: bool,
+    editor: String,
+    open_in_editor: bool,
+    cross_builder: CrossBuilder,
+    set_locale: bool,
+    locale: String,
+    target_builds: Arc<Mutex<Vec<TargetBuild>>>,
+    renderer: Renderer,
+    display_name: String,
+    crate_name: String,
+    class_prefix: String,
+    base_class: BaseClass,
+    flavor: ProjectFlavor,
+    layout: CrateLayout,
+    lib_scaffold: LibScaffold,
+    crate_location: CrateLocation,
+    classes: Vec<ClassSpec>,
+    class_list: String,
+    class_list_error: Option<String>,
+    new_class: ClassSpec,
+    new_class_icon: String,
+    prompt: Option<Prompt>,
+    preview_open: bool,
+    preview_selected: String,
+    gdextension_editor_open: bool,
+    gdextension_editor: GdExtensionEditor,
+    template_editor_open: bool,
+    template_editor_field: &'static str,
+    // Edits go straight into `templates` so the preview follows them, reloading the templates drops them
+    template_editor_edited: BTreeSet<&'static str>,
+    // Modification time of the local template file when it was last loaded, a newer one reloads it
+    template_modified: Option<SystemTime>,
+    template_checked: Instant,
+    reload_iterations: u32,
+    // The project being created, becomes `quick_start` once the creation succeeds
+    created_project: Option<(String, ProjectOptions)>,
+    quick_start: Option<(String, ProjectOptions)>,
+    presets: BTreeMap<String, Preset>,
+    // The preset being saved or the last one applied
+    preset_name: String,
+    recent_projects: Vec<RecentProject>,
+    theme: Theme,
+    language: Language,
+    check_for_updates: bool,
+    dismissed_release: String,
+    update_receiver: Option<oneshot::Receiver<Result<Option<Release>, String>>>,
+    // A release newer than this build, found by the startup check
+    available_update: Option<Release>,
+    // Added to `recent_projects` once the creation succeeds
+    pending_recent_project: Option<RecentProject>,
+    validation: Option<Validation>,
+}
+
+// What validate_settings last ran on, it renders the whole project and probes the disk so it only runs again once they change
+struct Validation {
+    project_name: String,
+    options: ProjectOptions,
+    templates: Option<ProjectTemplates>,
+    problems: Vec<String>,
 }
 
 impl Default for App {
     fn default() -> Self {
+        let (settings, settings_warning) = AppSettings::load();
         let mut app = Self {
-            godot_version: DEFAULT_GODOT_VERSION.to_string(),
-            reloadable: true,
-            targets: vec![
-                ("linux.debug.x86_64".to_string(), true),
-                ("linux.release.x86_64".to_string(), true),
-                ("windows.debug.x86_64".to_string(), true),
-                ("windows.release.x86_64".to_string(), true),
-                ("macos.debug".to_string(), true),
-                ("macos.release".to_string(), true),
-            ],
-            log: Arc::new(Mutex::new(String::new())),
-            is_creating: false,
+            godot_version: settings.godot_version,
+            reloadable: settings.reloadable,
+            entry_symbol: String::new(),
+            max_godot_version: String::new(),
+            targets: KNOWN_TARGETS
+                .iter()
+                .map(|target| (target.to_string(), settings.targets.iter().any(|selected| selected == target)))
+                .collect(),
+            library_paths: BTreeMap::new(),
+            target_profiles: BTreeMap::new(),
+            target_dir: String::new(),
+            extension_crates: String::new(),
+            template_variables: Vec::new(),
+            godot_features: KNOWN_GODOT_FEATURES.iter().map(|feature| (feature.to_string(), false)).collect(),
+            dependencies: KNOWN_DEPENDENCIES.iter().map(|(name, _, _)| (name.to_string(), false)).collect(),
+            log: Arc::new(Mutex::new(Log::default())),
+            log_level: LogLevel::Info,
+            log_save_path: None,
+            state: CreationState::Idle,
+            task_events: None,
+            cancel: Arc::new(AtomicBool::new(false)),
             templates: None,
+            post_create_hooks: settings.post_create_hooks.join("\n"),
+            template_hooks: false,
+            template_source: settings.template_source,
+            loaded_template_source: String::new(),
+            template_error: None,
+            template_receiver: None,
+            host_triple: None,
+            host_receiver: None,
+            prerequisites: Vec::new(),
+            prerequisite_receiver: None,
             project_name: String::new(),
             autofocus_input: true,
-            precompile_lib: false,
+            precompile_lib: settings.precompile_lib,
+            sign_libraries: false,
+            minisign_key: String::new(),
+            existing_project: ExistingProject::Refuse,
+            confirm_project_overwrite: false,
+            confirm_exit: false,
+            shutdown_started: None,
+            editor_plugin: false,
+            minimal_size: false,
+            release_profile: ReleaseProfile::default(),
+            double_precision: false,
+            prelude_module: false,
+            code_style: false,
+            itest: false,
+            class_icons: false,
+            git_init: false,
+            git_commit_message: DEFAULT_GIT_COMMIT_MESSAGE.to_string(),
+            license: License::None,
+            author: String::new(),
+            library_layout: LibraryLayout::TargetDir,
+            readme: true,
+            github_workflow: false,
+            export_presets: false,
+            task_runner: TaskRunner::None,
+            vscode: false,
+            env_vars: Vec::new(),
+            cargo: String::new(),
+            cargo_flags: String::new(),
+            godot: detect_godot_executable().unwrap_or_default(),
+            open_in_godot: false,
+            editor: String::new(),
+            open_in_editor: false,
+            cross_builder: CrossBuilder::Cargo,
+            set_locale: false,
+            locale: detect_system_locale().unwrap_or_default(),
+            target_builds: Arc::new(Mutex::new(Vec::new())),
+            renderer: Renderer::ForwardPlus,
+            display_name: String::new(),
+            crate_name: String::new(),
+            class_prefix: String::new(),
+            base_class: BaseClass::Node,
+            flavor: ProjectFlavor::Game,
+            layout: CrateLayout::Classes,
+            lib_scaffold: LibScaffold::Minimal,
+            crate_location: CrateLocation::Inside,
+            classes: Vec::new(),
+            class_list: String::new(),
+            class_list_error: None,
+            new_class: ClassSpec::default(),
+            new_class_icon: String::new(),
+            prompt: None,
+            preview_open: false,
+            preview_selected: String::new(),
+            gdextension_editor_open: false,
+            gdextension_editor: GdExtensionEditor::default(),
+            template_editor_open: false,
+            template_editor_field: EDITABLE_TEMPLATES[1],
+            template_editor_edited: BTreeSet::new(),
+            template_modified: None,
+            template_checked: Instant::now(),
+            reload_iterations: DEFAULT_RELOAD_ITERATIONS,
+            created_project: None,
+            quick_start: None,
+            presets: settings.presets,
+            recent_projects: settings.recent_projects,
+            theme: settings.theme,
+            language: settings.language,
+            check_for_updates: settings.check_for_updates,
+            dismissed_release: settings.dismissed_release,
+            update_receiver: None,
+            available_update: None,
+            pending_recent_project: None,
+            validation: None,
+            preset_name: String::new(),
         };
-        app.load_templates();
+        if let Some(warning) = settings_warning {
+            app.log.lock().unwrap().warn(&warning);
+        }
+        app.load_templates(false);
+        app.detect_host();
+        app.run_prerequisite_checks();
+        if app.check_for_updates {
+            app.start_update_check();
+        }
         app
     }
 }
 
 impl eframe::App for App {
     fn update(&mut self, ctx: &egui::Context, _frame: &mut eframe::Frame) {
-        let is_creating = Arc::new(Mutex::new(false));
-        self.is_creating = *is_creating.lock().unwrap();
+        self.poll_templates();
+        self.watch_templates();
+        self.poll_host();
+        self.poll_prerequisites();
+        self.poll_update_check();
+        self.poll_creation_state();
+        self.handle_close_request(ctx);
+        self.handle_shortcuts(ctx);
+        let is_running = self.state == CreationState::Running;
 
         egui::CentralPanel::default().show(ctx, |ui| {
+            self.show_theme(ui, ctx);
+            self.show_language(ui, ctx);
+            self.show_update_check(ui, ctx);
+            self.show_templates_status(ui);
+            self.show_prerequisites(ui);
+            ui.add_enabled_ui(!is_running, |ui| self.show_recent_projects(ui));
             ui.horizontal(|ui| {
-                self.show_project_name(ui);
-                if !self.is_creating {
-                    if ui.button("Create Project").clicked() {
-                        ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(egui::WindowLevel::AlwaysOnTop));
-                        self.is_creating = true;
-                        let log_clone = Arc::clone(&self.log);
-                        let project_name = self.project_name.clone();
-                        let templates = self.templates.clone();
-                        let godot_version = self.godot_version.clone();
-                        let reloadable = self.reloadable;
-                        let targets: Vec<String> = self
-                            .targets
-                            .iter()
-                            .filter_map(|(target, is_selected)| if *is_selected { Some(target.clone()) } else { None })
-                            .collect();
-
-                        // Spawn a new thread for project creation
-                        let log_clone_inner = Arc::clone(&log_clone);
-                        let log_clone_inner_clone = Arc::clone(&log_clone_inner);
-                        let precompile_lib = self.precompile_lib;
-
-                        thread::spawn(move || {
-                            let result = handle_create_project(
-                                &project_name,
-                                log_clone_inner_clone,
-                                templates.as_ref(),
-                                &godot_version,
-                                reloadable,
-                                &targets,
-                                precompile_lib,
-                            );
-
-                            let mut log_inner = log_clone_inner.lock().unwrap();
-                            if let Err(err) = result {
-                                log_inner.push_str(&format!("Error: {}\n", err))
-                            }
-                        });
+                let submitted = ui.add_enabled_ui(!is_running, |ui| self.show_project_name(ui)).inner;
+                if !is_running {
+                    let problems = self.creation_problems();
+                    let create = ui
+                        .add_enabled(problems.is_empty(), egui::Button::new(tr("Create Project")))
+                        .on_disabled_hover_text(problems.join("\n"));
+                    if create.clicked() || (submitted && problems.is_empty()) {
+                        if self.existing_project == ExistingProject::Overwrite && project_exists(&self.project_name) {
+                            self.confirm_project_overwrite = true;
+                        } else {
+                            self.start_creation(ctx);
+                        }
+                    }
+                    if ui.selectable_label(self.preview_open, tr("Preview")).clicked() {
+                        self.preview_open = !self.preview_open;
+                    }
+                    if ui.selectable_label(self.template_editor_open, tr("Templates")).clicked() {
+                        self.template_editor_open = !self.template_editor_open;
+                    }
+                    if ui.selectable_label(self.gdextension_editor_open, ".gdextension").clicked() {
+                        self.gdextension_editor_open = !self.gdextension_editor_open;
+                        // Suggest the project in the name field, the way the other tools look it up
+                        if self.gdextension_editor.path.is_empty() && project_exists(&self.project_name) {
+                            self.gdextension_editor.path = find_gdextension(&self.project_name).display().to_string();
+                        }
                     }
                 } else {
                     show_creation_progress(ui);
+                    if ui
+                        .add_enabled(!self.cancel.load(Ordering::Relaxed), egui::Button::new(tr("Cancel")))
+                        .on_hover_text("Esc")
+                        .clicked()
+                    {
+                        self.cancel_task();
+                    }
                 }
             });
+            if !is_running {
+                self.show_name_problems(ui);
+            }
 
-            self.show_godot_version(ui);
-            self.show_reloadable_checkbox(ui);
-            self.show_targets_group(ui);
-            ui.checkbox(&mut self.precompile_lib, "Precompile Rust Library and GdExtension (this takes a while)");
+            ui.add_enabled_ui(!is_running, |ui| {
+                self.show_presets(ui);
+                self.show_godot_version(ui);
+                self.show_project_settings(ui);
+                self.show_reloadable_checkbox(ui);
+                self.show_gdextension_configuration(ui);
+                self.show_existing_project(ui);
+                self.show_targets_group(ui);
+                self.show_godot_features_group(ui);
+                self.show_dependencies_group(ui);
+                self.show_classes(ui);
+                self.show_template_variables(ui);
+                ui.checkbox(&mut self.editor_plugin, tr("Add \"Rebuild Rust\" editor plugin"));
+                ui.checkbox(&mut self.double_precision, tr("Double precision (needs a Godot build with precision=double)"));
+                ui.checkbox(&mut self.prelude_module, tr("Shared prelude module with node and signal helper macros"));
+                ui.checkbox(&mut self.code_style, tr("Code style: rustfmt.toml, clippy.toml and lints in Cargo.toml"));
+                ui.checkbox(&mut self.itest, tr("Integration tests run in a headless Godot (run_itest.sh)"));
+                ui.checkbox(&mut self.class_icons, tr("Editor icons for the Rust classes in the Create Node dialog"));
+                ui.checkbox(&mut self.readme, tr("README.md describing the layout and how to build"));
+                self.show_git_init(ui);
+                self.show_post_create_hooks(ui);
+                ui.checkbox(&mut self.github_workflow, tr("GitHub Actions workflow building every target"));
+                ui.checkbox(&mut self.export_presets, tr("Godot export presets for the selected platforms"));
+                ui.checkbox(&mut self.vscode, tr("VS Code settings, build tasks and CodeLLDB debug configurations"));
+                ui.horizontal(|ui| {
+                    ui.label(tr("Build recipes:"));
+                    ui.radio_value(&mut self.task_runner, TaskRunner::None, tr("None"));
+                    ui.radio_value(&mut self.task_runner, TaskRunner::Just, "justfile");
+                    ui.radio_value(&mut self.task_runner, TaskRunner::Make, "Makefile");
+                });
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(tr("Library paths:"));
+                    ui.radio_value(&mut self.library_layout, LibraryLayout::TargetDir, tr("cargo's target dir"));
+                    ui.radio_value(
+                        &mut self.library_layout,
+                        LibraryLayout::Bin,
+                        tr("bin/<platform>/, filled by copy_libs scripts and after builds"),
+                    );
+                    ui.radio_value(&mut self.library_layout, LibraryLayout::Split, tr("target dir in the editor, bin/ in exports"));
+                });
+                ui.horizontal(|ui| {
+                    ui.label(tr("Cargo target dir:"));
+                    ui.add(egui::TextEdit::singleline(&mut self.target_dir).hint_text(tr("target (relative to the crate)")));
+                });
+                ui.checkbox(
+                    &mut self.minimal_size,
+                    tr("Minimal size release profile (opt-level \"z\", LTO, panic = \"abort\", strip)"),
+                );
+                ui.add_enabled_ui(!self.minimal_size, |ui| self.show_release_profile(ui));
+                ui.checkbox(&mut self.precompile_lib, tr("Precompile Rust Library and GdExtension (this takes a while)"));
+                ui.horizontal(|ui| {
+                    ui.label(tr("Other platforms build with:"));
+                    ui.radio_value(&mut self.cross_builder, CrossBuilder::Cargo, "cargo --target");
+                    ui.radio_value(&mut self.cross_builder, CrossBuilder::Cross, "cross");
+                    ui.radio_value(&mut self.cross_builder, CrossBuilder::Zigbuild, "cargo-zigbuild");
+                });
+                ui.horizontal(|ui| {
+                    ui.checkbox(&mut self.open_in_godot, tr("Open in Godot when done"));
+                    ui.checkbox(&mut self.open_in_editor, tr("Open the Rust crate in the code editor when done"));
+                });
+                self.show_signing(ui);
+                self.show_env_vars(ui);
+                self.show_reload_stress_test(ui, ctx);
+                self.show_add_class(ui, ctx);
+            });
+            self.show_creation_state(ui);
+            self.show_target_builds(ui);
+            self.show_quick_start(ui, ctx);
             self.show_log(ui);
         });
 
+        self.show_prompt(ctx);
+        self.show_project_overwrite_prompt(ctx);
+        self.show_exit_prompt(ctx);
+        self.show_log_save_prompt(ctx);
+        self.show_preview(ctx);
+        self.show_template_editor(ctx);
+        self.show_gdextension_editor(ctx);
+
         ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(egui::WindowLevel::Normal));
         ctx.request_repaint(); // Request UI to repaint to reflect log changes
     }
 }
 
 impl App {
-    fn load_templates(&mut self) {
-        const TEMPLATE_FILE: &str = "templates.yaml";
-        if let Ok(content) = std::fs::read_to_string(TEMPLATE_FILE) {
-            self.templates = serde_yaml::from_str::<ProjectTemplates>(&content).ok();
-        } else {
-            eprintln!("Failed to load templates.");
+    // Remote templates come from the cache unless `refresh` fetches them again
+    fn load_templates(&mut self, refresh: bool) {
+        let (sender, receiver) = oneshot::channel();
+        self.template_receiver = Some(receiver);
+        self.loaded_template_source = self.template_source.trim().to_string();
+        self.template_modified = self.local_template_path().and_then(|path| modified_time(&path));
+        let source = TemplateSource::parse(&self.template_source);
+        let log = Arc::clone(&self.log);
+        tokio::spawn(async move {
+            let resolved = tokio::task::spawn_blocking(move || source.resolve(refresh))
+                .await
+                .unwrap_or_else(|err| Err(tr_fmt("Template fetching stopped unexpectedly: {}", &[&err])));
+            let templates = match resolved {
+                Ok((path, warning)) => {
+                    if let Some(warning) = warning {
+                        log.lock().unwrap().warn(&warning);
+                    }
+                    read_templates(&path.to_string_lossy()).await
+                }
+                Err(err) => Err(err),
+            };
+            let _ = sender.send(templates);
+        });
+    }
+
+    fn poll_templates(&mut self) {
+        let Some(template_receiver) = &mut self.template_receiver else {
+            return;
+        };
+
+        match template_receiver.try_recv() {
+            Ok(Ok(templates)) => {
+                self.templates = Some(templates);
+                self.template_error = None;
+                self.template_editor_edited.clear();
+            }
+            Ok(Err(err)) => self.template_error = Some(err),
+            Err(oneshot::error::TryRecvError::Closed) => self.template_error = Some(tr("Template loading stopped unexpectedly.").to_string()),
+            Err(oneshot::error::TryRecvError::Empty) => return,
         }
+        self.template_receiver = None;
     }
 
-    fn show_project_name(&mut self, ui: &mut egui::Ui) {
-        ui.label("Project Name:");
-        let pn = ui.text_edit_singleline(&mut self.project_name);
-        if self.autofocus_input {
-            pn.request_focus();
-            self.autofocus_input = false;
+    // Picks up edits from an external editor, the window's own unsaved edits aren't thrown away for them
+    fn watch_templates(&mut self) {
+        if self.template_checked.elapsed() < TEMPLATE_WATCH_INTERVAL || self.template_receiver.is_some() || !self.template_editor_edited.is_empty() {
+            return;
+        }
+        self.template_checked = Instant::now();
+        let Some(path) = self.local_template_path() else {
+            return;
+        };
+        let modified = modified_time(&path);
+        if modified.is_some() && modified != self.template_modified {
+            self.log.lock().unwrap().info(&format!("{} changed, reloading the templates.", path));
+            self.load_templates(false);
         }
     }
 
-    fn show_godot_version(&mut self, ui: &mut egui::Ui) {
+    // Remote templates live in a cache that the next refresh overwrites, so they are neither watched nor saved to
+    fn local_template_path(&self) -> Option<String> {
+        match TemplateSource::parse(&self.loaded_template_source) {
+            TemplateSource::File(path) => Some(path),
+            _ => None,
+        }
+    }
+
+    fn detect_host(&mut self) {
+        let (sender, receiver) = oneshot::channel();
+        self.host_receiver = Some(receiver);
+        tokio::task::spawn_blocking(move || {
+            let _ = sender.send(detect_host_triple());
+        });
+    }
+
+    fn poll_host(&mut self) {
+        let Some(host_receiver) = &mut self.host_receiver else {
+            return;
+        };
+
+        match host_receiver.try_recv() {
+            Ok(host_triple) => self.host_triple = host_triple,
+            Err(oneshot::error::TryRecvError::Closed) => {}
+            Err(oneshot::error::TryRecvError::Empty) => return,
+        }
+        self.host_receiver = None;
+    }
+
+    // Runs on the blocking pool, every check starts a process
+    fn run_prerequisite_checks(&mut self) {
+        let (sender, receiver) = oneshot::channel();
+        self.prerequisite_receiver = Some(receiver);
+        let options = self.project_options();
+        let template_source = TemplateSource::parse(&self.template_source);
+        tokio::task::spawn_blocking(move || {
+            let _ = sender.send(check_prerequisites(&options, &template_source));
+        });
+    }
+
+    fn poll_prerequisites(&mut self) {
+        let Some(prerequisite_receiver) = &mut self.prerequisite_receiver else {
+            return;
+        };
+
+        match prerequisite_receiver.try_recv() {
+            Ok(checks) => self.prerequisites = checks,
+            Err(oneshot::error::TryRecvError::Closed) => {}
+            Err(oneshot::error::TryRecvError::Empty) => return,
+        }
+        self.prerequisite_receiver = None;
+    }
+
+    fn show_prerequisites(&mut self, ui: &mut egui::Ui) {
+        let failed = self.prerequisites.iter().filter(|check| !check.passed).count();
+        let title = if self.prerequisite_receiver.is_some() {
+            tr("Prerequisites: checking...").to_string()
+        } else if failed > 0 {
+            tr_fmt("Prerequisites: {} failed", &[&failed])
+        } else {
+            tr("Prerequisites: all passed").to_string()
+        };
+
+        egui::CollapsingHeader::new(title).id_salt("prerequisites").show(ui, |ui| {
+            for check in &self.prerequisites {
+                ui.horizontal_wrapped(|ui| {
+                    if check.passed {
+                        ui.colored_label(egui::Color32::GREEN, "✔");
+                    } else {
+                        ui.colored_label(egui::Color32::RED, "✘");
+                    }
+                    ui.strong(check.name.as_str());
+                    ui.label(check.detail.as_str());
+                });
+                if !check.passed {
+                    ui.label(format!("    {}", check.hint));
+                }
+            }
+            // Targets and executables change in the settings below, so the checks are rerun on demand
+            if ui
+                .add_enabled(self.prerequisite_receiver.is_none(), egui::Button::new(tr("Check again")))
+                .clicked()
+            {
+                self.run_prerequisite_checks();
+            }
+        });
+    }
+
+    pub fn apply_theme(&self, ctx: &egui::Context) {
+        ctx.set_theme(match self.theme {
+            Theme::System => egui::ThemePreference::System,
+            Theme::Dark => egui::ThemePreference::Dark,
+            Theme::Light => egui::ThemePreference::Light,
+        });
+    }
+
+    // Saved right away like presets, so the choice also holds if the window is killed
+    fn show_theme(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
         ui.horizontal(|ui| {
-            ui.label("Godot Version:");
-            ui.text_edit_singleline(&mut self.godot_version);
+            ui.label(tr("Theme:"));
+            let previous = self.theme;
+            ui.radio_value(&mut self.theme, Theme::System, tr("System"));
+            ui.radio_value(&mut self.theme, Theme::Dark, tr("Dark"));
+            ui.radio_value(&mut self.theme, Theme::Light, tr("Light"));
+            if self.theme != previous {
+                self.apply_theme(ctx);
+                self.save_settings(ctx);
+            }
         });
     }
 
-    fn show_reloadable_checkbox(&mut self, ui: &mut egui::Ui) {
-        ui.checkbox(&mut self.reloadable, "Reloadable");
+    // Takes effect from the next frame, the window title only after a restart
+    fn show_language(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        ui.horizontal(|ui| {
+            ui.label(tr("Language:"));
+            let previous = self.language;
+            for language in Language::ALL {
+                ui.radio_value(&mut self.language, language, language.label());
+            }
+            if self.language != previous {
+                set_language(self.language);
+                self.save_settings(ctx);
+            }
+        });
     }
 
-    fn show_targets_group(&mut self, ui: &mut egui::Ui) {
+    fn start_update_check(&mut self) {
+        let (sender, receiver) = oneshot::channel();
+        self.update_receiver = Some(receiver);
+        tokio::task::spawn_blocking(move || {
+            let _ = sender.send(check_for_update());
+        });
+    }
+
+    // Offline isn't worth more than a line in the log, the check runs again next start
+    fn poll_update_check(&mut self) {
+        let Some(update_receiver) = &mut self.update_receiver else {
+            return;
+        };
+
+        match update_receiver.try_recv() {
+            Ok(Ok(release)) => self.available_update = release.filter(|release| release.version != self.dismissed_release),
+            Ok(Err(err)) => self.log.lock().unwrap().info(&format!("Checking for updates failed: {}", err)),
+            Err(oneshot::error::TryRecvError::Closed) => {}
+            Err(oneshot::error::TryRecvError::Empty) => return,
+        }
+        self.update_receiver = None;
+    }
+
+    // The setting, and a banner with the release notes once a newer release is found
+    fn show_update_check(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        if ui.checkbox(&mut self.check_for_updates, tr("Check for updates on startup")).changed() {
+            if self.check_for_updates && self.update_receiver.is_none() {
+                self.start_update_check();
+            }
+            self.save_settings(ctx);
+        }
+        let Some(release) = &self.available_update else {
+            return;
+        };
+
+        let mut dismissed = false;
         ui.group(|ui| {
-            ui.label("Targets:");
-            for (target, is_selected) in &mut self.targets {
-                ui.checkbox(is_selected, target.clone());
+            ui.horizontal_wrapped(|ui| {
+                ui.strong(tr_fmt(
+                    "gen_gdext {} is available, this is {}.",
+                    &[&release.version, &env!("CARGO_PKG_VERSION")],
+                ));
+                ui.hyperlink_to(tr("Release page"), &release.url);
+                dismissed = ui.small_button(tr("Dismiss")).clicked();
+            });
+            if !release.notes.is_empty() {
+                egui::CollapsingHeader::new(tr("What's new")).id_salt("release_notes").show(ui, |ui| {
+                    egui::ScrollArea::vertical()
+                        .id_salt("release_notes")
+                        .max_height(RELEASE_NOTES_HEIGHT)
+                        .show(ui, |ui| {
+                            ui.label(release.notes.as_str());
+                        });
+                });
             }
         });
+        if dismissed {
+            self.dismissed_release = release.version.clone();
+            self.available_update = None;
+            self.save_settings(ctx);
+        }
     }
 
-    fn show_log(&mut self, ui: &mut egui::Ui) {
+    fn show_templates_status(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            if self.template_receiver.is_some() {
+                ui.spinner();
+                ui.label(tr("Loading templates..."));
+            } else if let Some(err) = &self.template_error {
+                ui.colored_label(egui::Color32::RED, err);
+            } else {
+                let gdext = self
+                    .templates
+                    .as_ref()
+                    .and_then(|templates| templates.requirements.gdext.as_deref())
+                    .map(|gdext| tr_fmt(", written for gdext {}", &[&gdext]))
+                    .unwrap_or_default();
+                let source = match self.loaded_template_source.as_str() {
+                    "" => TEMPLATE_FILE,
+                    source => source,
+                };
+                ui.label(tr_fmt("Templates loaded from {}{}", &[&source, &gdext]));
+            }
+
+            let reload = if TemplateSource::parse(&self.loaded_template_source).is_remote() {
+                tr("Refresh templates")
+            } else {
+                tr("Reload templates")
+            };
+            if ui.add_enabled(self.template_receiver.is_none(), egui::Button::new(reload)).clicked() {
+                self.load_templates(true);
+            }
+        });
+        if let (Some(templates), None) = (&self.templates, &self.template_error) {
+            for warning in template_warnings(templates, &self.project_options().template_variables) {
+                ui.colored_label(egui::Color32::YELLOW, warning);
+            }
+        }
+        ui.horizontal(|ui| {
+            ui.label(tr("Template source:"));
+            let response = ui.add(egui::TextEdit::singleline(&mut self.template_source).hint_text(tr("templates.yaml, a git or an https URL")));
+            if response.lost_focus() && self.template_source.trim() != self.loaded_template_source {
+                self.load_templates(false);
+            }
+        });
+    }
+
+    fn start_creation(&mut self, ctx: &egui::Context) {
+        ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(egui::WindowLevel::AlwaysOnTop));
+        let log_clone = Arc::clone(&self.log);
+        let project_name = self.project_name.clone();
+        let templates = self.templates.clone();
+        let options = self.project_options();
+        self.created_project = Some((project_name.clone(), options.clone()));
+        self.quick_start = None;
+        self.pending_recent_project = std::env::current_dir().ok().map(|dir| RecentProject {
+            name: project_name.clone(),
+            path: dir.join(&project_name).to_string_lossy().into_owned(),
+            preset: self.current_preset(),
+        });
+        let builds = Arc::clone(&self.target_builds);
+        builds.lock().unwrap().clear();
+
+        self.start_task(tr("Project created successfully."), tr("Creation failed"), move |cancel, asker| {
+            let confirm_overwrite = |path: &str| {
+                let message = tr_fmt("'{}' differs from the generated version. Overwrite it?", &[&path]);
+                asker.ask(tr("File conflict"), message, tr("Overwrite"), tr("Keep existing"))
+            };
+            let confirm_collisions = |warnings: &[String]| {
+                let message = tr_fmt("{}\nCreate the project anyway?", &[&warnings.join("\n")]);
+                asker.ask(tr("Name collision"), message, tr("Create anyway"), tr("Cancel"))
+            };
+            handle_create_project(
+                &project_name,
+                log_clone,
+                templates.as_ref(),
+                &options,
+                cancel,
+                &CreationPrompts {
+                    overwrite: &confirm_overwrite,
+                    collisions: &confirm_collisions,
+                    rust_targets: &|triples| confirm_rust_targets(asker, triples),
+                },
+                &builds,
+            )
+        });
+    }
+
+    fn start_add_class(&mut self, ctx: &egui::Context) {
+        ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(egui::WindowLevel::AlwaysOnTop));
+        let log_clone = Arc::clone(&self.log);
+        let project_name = self.project_name.clone();
+        let templates = self.templates.clone();
+        let class = self.new_class.clone();
+        let icon = self.new_class_icon.clone();
+
+        self.start_task(tr("Class added successfully."), tr("Adding class failed"), move |_, _| {
+            let templates = templates.ok_or(CreateError::TemplatesUnavailable)?;
+            add_class_to_project(&project_name, &templates, &class, &icon, &log_clone)
+        });
+    }
+
+    fn start_reload_stress_test(&mut self, ctx: &egui::Context) {
+        ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(egui::WindowLevel::AlwaysOnTop));
+        let log_clone = Arc::clone(&self.log);
+        let project_name = self.project_name.clone();
+        let iterations = self.reload_iterations;
+        let options = self.project_options();
+
+        self.start_task(tr("Reload stress test passed."), tr("Reload stress test failed"), move |cancel, _| {
+            if project_name.is_empty() {
+                return Err(CreateError::EmptyName);
+            }
+            stress_test_reload(&project_name, &options, iterations, &log_clone, cancel)
+        });
+    }
+
+    // The blocking work runs on tokio's blocking pool, a task awaits it so even a panic ends in a `Finished` event
+    fn start_task<F>(&mut self, success_message: &'static str, failure_prefix: &'static str, task: F)
+    where
+        F: FnOnce(&AtomicBool, &Asker) -> Result<(), CreateError> + Send + 'static,
+    {
+        self.cancel.store(false, Ordering::Relaxed);
+        let cancel = Arc::clone(&self.cancel);
+        let log_clone = Arc::clone(&self.log);
+
+        let (events, task_events) = mpsc::unbounded_channel();
+        self.state = CreationState::Running;
+        self.task_events = Some(task_events);
+
+        tokio::spawn(async move {
+            let asker = Asker(events.clone());
+            let outcome = tokio::task::spawn_blocking(move || task(&cancel, &asker)).await;
+            let state = match outcome {
+                Ok(Ok(())) => CreationState::Done(success_message.to_string()),
+                Ok(Err(err)) => {
+                    log_clone.lock().unwrap().error(&err.to_string());
+                    CreationState::Failed(format!("{}: {}", failure_prefix, err))
+                }
+                Err(err) => {
+                    log_clone.lock().unwrap().error(&format!("The task stopped unexpectedly: {}", err));
+                    CreationState::Failed(tr_fmt("{}: the task stopped unexpectedly.", &[&failure_prefix]))
+                }
+            };
+            let _ = events.send(TaskEvent::Finished(state));
+        });
+    }
+
+    fn poll_creation_state(&mut self) {
+        let Some(task_events) = &mut self.task_events else {
+            return;
+        };
+
+        let state = loop {
+            match task_events.try_recv() {
+                // A question arriving after a cancel is answered with "no" right away by dropping it
+                Ok(TaskEvent::Prompt(prompt)) => {
+                    if !self.cancel.load(Ordering::Relaxed) {
+                        self.prompt = Some(prompt);
+                    }
+                }
+                Ok(TaskEvent::Finished(state)) => break state,
+                // Only happens if the runtime shut down under the task
+                Err(mpsc::error::TryRecvError::Disconnected) => break CreationState::Failed(tr("The task stopped unexpectedly.").to_string()),
+                Err(mpsc::error::TryRecvError::Empty) => return,
+            }
+        };
+        self.task_events = None;
+        self.prompt = None;
+
+        // The task may have created the project or its files, which changes what validation finds
+        self.validation = None;
+
+        // Only a successful creation turns into the quick-start checklist
+        let created = self.created_project.take();
+        let recent = self.pending_recent_project.take();
+        if matches!(state, CreationState::Done(_)) && created.is_some() {
+            self.quick_start = created;
+            if let Some(recent) = recent {
+                self.add_recent_project(recent);
+            }
+        }
+        self.state = state;
+    }
+
+    fn start_build(&mut self, ctx: &egui::Context, project_name: String, options: ProjectOptions) {
+        ctx.send_viewport_cmd(egui::ViewportCommand::WindowLevel(egui::WindowLevel::AlwaysOnTop));
+        let log_clone = Arc::clone(&self.log);
+        let builds = Arc::clone(&self.target_builds);
+        builds.lock().unwrap().clear();
+        self.start_task(tr("Library built."), tr("Build failed"), move |cancel, asker| {
+            offer_rust_targets(&log_clone, &options, cancel, &|triples| confirm_rust_targets(asker, triples))?;
+            precompile_library(&project_name, &log_clone, &options, cancel, &builds)
+        });
+    }
+
+    fn show_quick_start(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        let Some((project_name, options)) = self.quick_start.clone() else {
+            return;
+        };
+        let is_running = self.state == CreationState::Running;
+
+        let mut clicked = None;
         ui.group(|ui| {
-            ui.label("Log:");
-            egui::ScrollArea::vertical().max_height(LOG_MAX_HEIGHT).show(ui, |ui| {
-                let mut log_content = self.log.lock().unwrap();
-                ui.add_sized(
-                    egui::vec2(LOG_TEXT_WIDTH, LOG_MAX_HEIGHT),
-                    egui::TextEdit::multiline(&mut *log_content)
-                        .desired_rows(10)
-                        .hint_text(PROJECT_NAME_HINT)
-                        .interactive(false),
-                );
+            ui.horizontal(|ui| {
+                ui.strong(tr_fmt("Next steps for {}", &[&project_name]));
+                if ui.small_button(tr("Dismiss")).clicked() {
+                    self.quick_start = None;
+                }
             });
+            for (index, step) in quick_start_steps(&project_name, &options).into_iter().enumerate() {
+                ui.horizontal_wrapped(|ui| {
+                    ui.label(format!("{}. {}", index + 1, step.text));
+                    for action in step.actions {
+                        let label = match &action {
+                            QuickStartAction::Copy(_) => tr("Copy"),
+                            QuickStartAction::Build => tr("Build now"),
+                            QuickStartAction::OpenInGodot => tr("Open in Godot"),
+                            QuickStartAction::OpenInEditor => tr("Open in editor"),
+                        };
+                        if ui.add_enabled(!is_running, egui::Button::new(label).small()).clicked() {
+                            clicked = Some(action);
+                        }
+                    }
+                });
+            }
         });
+
+        match clicked {
+            Some(QuickStartAction::Copy(text)) => ctx.copy_text(text),
+            Some(QuickStartAction::Build) => self.start_build(ctx, project_name, options),
+            Some(QuickStartAction::OpenInGodot) => {
+                if let Err(err) = open_in_editor(&project_name) {
+                    self.state = CreationState::Failed(tr_fmt("Opening Godot failed: {}", &[&err]));
+                }
+            }
+            Some(QuickStartAction::OpenInEditor) => {
+                if let Err(err) = open_in_code_editor(&project_name) {
+                    self.state = CreationState::Failed(tr_fmt("Opening the code editor failed: {}", &[&err]));
+                }
+            }
+            None => {}
+        }
     }
-}
 
-fn handle_create_project(
-    project_name: &str,
-    log_clone: Arc<Mutex<String>>,
-    templates: Option<&ProjectTemplates>,
-    godot_version: &str,
-    reloadable: bool,
-    targets: &[String],
-    precompile_lib: bool,
-) -> Result<(), String> {
-    if project_name.is_empty() {
-        return Err("Project name cannot be empty.".to_string());
+    fn show_target_builds(&self, ui: &mut egui::Ui) {
+        let builds = self.target_builds.lock().unwrap();
+        if builds.is_empty() {
+            return;
+        }
+
+        ui.group(|ui| {
+            for build in builds.iter() {
+                ui.horizontal(|ui| {
+                    let color = match build.status {
+                        TargetStatus::Pending => egui::Color32::GRAY,
+                        TargetStatus::Building => egui::Color32::YELLOW,
+                        TargetStatus::Built => egui::Color32::GREEN,
+                        TargetStatus::Failed(_) => egui::Color32::RED,
+                    };
+                    ui.label(build.target.as_str());
+                    ui.colored_label(color, build.status.to_string());
+                    if build.status == TargetStatus::Building {
+                        // Without a package count from cargo metadata there is nothing to measure against
+                        if build.crates_total > 0 {
+                            let fraction = build.crates_built.min(build.crates_total) as f32 / build.crates_total as f32;
+                            ui.add(
+                                egui::ProgressBar::new(fraction)
+                                    .desired_width(BUILD_PROGRESS_WIDTH)
+                                    .text(tr_fmt("{}/{} crates", &[&build.crates_built, &build.crates_total])),
+                            );
+                        } else {
+                            ui.spinner();
+                            ui.label(tr_fmt("{} crates", &[&build.crates_built]));
+                        }
+                    }
+                });
+            }
+        });
     }
 
-    if project_exists(project_name) {
-        return Err("Project with this name already exists.".to_string());
+    fn show_creation_state(&self, ui: &mut egui::Ui) {
+        match &self.state {
+            CreationState::Idle | CreationState::Running => {}
+            CreationState::Done(message) => {
+                ui.colored_label(egui::Color32::GREEN, message.as_str());
+            }
+            CreationState::Failed(message) => {
+                ui.colored_label(egui::Color32::RED, message.as_str());
+            }
+        }
     }
 
-    {
-        let mut log_inner = log_clone.lock().unwrap();
-        log_inner.push_str("Creating project...\n");
+    fn project_options(&self) -> ProjectOptions {
+        ProjectOptions {
+            godot_version: self.godot_version.parse().unwrap_or_default(),
+            entry_symbol: self.entry_symbol.trim().to_string(),
+            compatibility_maximum: self.max_godot_version.parse().ok(),
+            target_dir: self.target_dir.trim().to_string(),
+            extension_crates: self
+                .extension_crates
+                .split(',')
+                .map(str::trim)
+                .filter(|crate_name| !crate_name.is_empty())
+                .map(str::to_string)
+                .collect(),
+            template_variables: self
+                .template_variables
+                .iter()
+                .filter(|(name, _)| !name.trim().is_empty())
+                .map(|(name, value)| (name.trim().to_string(), value.clone()))
+                .collect(),
+            library_paths: self
+                .library_paths
+                .iter()
+                .filter(|(_, path)| !path.trim().is_empty())
+                .map(|(target, path)| (target.clone(), path.trim().to_string()))
+                .collect(),
+            target_profiles: self
+                .target_profiles
+                .iter()
+                .filter(|(_, profile)| !profile.trim().is_empty())
+                .map(|(target, profile)| (target.clone(), profile.trim().to_string()))
+                .collect(),
+            reloadable: self.reloadable,
+            targets: self
+                .targets
+                .iter()
+                .filter_map(|(target, is_selected)| if *is_selected { Some(target.clone()) } else { None })
+                .collect(),
+            precompile_lib: self.precompile_lib,
+            sign_libraries: self.sign_libraries,
+            minisign_key: self.minisign_key.clone(),
+            existing_project: self.existing_project,
+            editor_plugin: self.editor_plugin,
+            minimal_size: self.minimal_size,
+            release_profile: self.release_profile.clone(),
+            double_precision: self.double_precision,
+            prelude_module: self.prelude_module,
+            code_style: self.code_style,
+            itest: self.itest,
+            class_icons: self.class_icons,
+            git_init: self.git_init,
+            git_commit_message: self.git_commit_message.clone(),
+            license: self.license,
+            author: self.author.clone(),
+            library_layout: self.library_layout,
+            readme: self.readme,
+            github_workflow: self.github_workflow,
+            export_presets: self.export_presets,
+            task_runner: self.task_runner,
+            vscode: self.vscode,
+            godot_features: self
+                .godot_features
+                .iter()
+                .filter_map(|(feature, is_selected)| if *is_selected { Some(feature.clone()) } else { None })
+                .collect(),
+            dependencies: self
+                .dependencies
+                .iter()
+                .filter_map(|(name, is_selected)| if *is_selected { Some(name.clone()) } else { None })
+                .collect(),
+            dependency_versions: BTreeMap::new(),
+            post_create_hooks: self
+                .post_create_hooks
+                .lines()
+                .map(str::trim)
+                .filter(|hook| !hook.is_empty())
+                .map(str::to_string)
+                .collect(),
+            template_hooks: self.template_hooks,
+            env: self.env_vars.iter().filter(|(key, _)| !key.trim().is_empty()).cloned().collect(),
+            cargo: self.cargo.trim().to_string(),
+            cargo_args: self.cargo_flags.split_whitespace().map(str::to_string).collect(),
+            godot: self.godot.trim().to_string(),
+            open_in_godot: self.open_in_godot,
+            editor: self.editor.trim().to_string(),
+            open_in_editor: self.open_in_editor,
+            cross_builder: self.cross_builder,
+            locale: if self.set_locale { self.locale.trim().to_string() } else { String::new() },
+            renderer: self.renderer,
+            display_name: self.display_name.clone(),
+            crate_name: self.crate_name.trim().to_string(),
+            class_prefix: self.class_prefix.trim().to_string(),
+            base_class: self.base_class,
+            classes: self.classes.clone(),
+            flavor: self.flavor,
+            layout: self.layout,
+            lib_scaffold: self.lib_scaffold,
+            crate_location: self.crate_location,
+        }
     }
 
-    // Call the actual function to create the project
-    let templates = match templates {
-        Some(templates) => templates,
-        None => return Err("Templates are not available.".to_string()),
-    };
+    // Everything that would make creation fail right away, the Create button stays disabled until it's empty
+    fn creation_problems(&mut self) -> Vec<String> {
+        let mut problems = Vec::new();
+        if let Err(err) = self.godot_version.parse::<GodotVersion>() {
+            problems.push(err);
+        }
+        if !self.max_godot_version.trim().is_empty() {
+            if let Err(err) = self.max_godot_version.parse::<GodotVersion>() {
+                problems.push(err);
+            }
+        }
+        if let Some(err) = &self.template_error {
+            problems.push(err.clone());
+        }
+        let options = self.project_options();
+        let is_current = self
+            .validation
+            .as_ref()
+            .is_some_and(|last| last.project_name == self.project_name && last.options == options && last.templates == self.templates);
+        if !is_current {
+            self.validation = Some(Validation {
+                problems: validate_settings(&self.project_name, self.templates.as_ref(), &options),
+                project_name: self.project_name.clone(),
+                options,
+                templates: self.templates.clone(),
+            });
+        }
+        problems.extend(self.validation.iter().flat_map(|validation| validation.problems.iter().cloned()));
+        problems
+    }
+
+    // True when Enter was pressed in the field, which creates the project like the button does
+    fn show_project_name(&mut self, ui: &mut egui::Ui) -> bool {
+        ui.label(tr("Project Name:"));
+        let pn = ui.text_edit_singleline(&mut self.project_name);
+        if self.autofocus_input {
+            pn.request_focus();
+            self.autofocus_input = false;
+        }
+        pn.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter))
+    }
 
-    create_project(project_name, log_clone, templates, godot_version, reloadable, targets, precompile_lib)?;
+    // Shown under the name as it's typed, an empty name only disables the Create button
+    fn show_name_problems(&self, ui: &mut egui::Ui) {
+        if self.project_name.is_empty() {
+            return;
+        }
+        for problem in name_problems(&self.project_name, &self.project_options()) {
+            ui.colored_label(egui::Color32::RED, problem);
+        }
+    }
 
-    Ok(())
-}
+    fn show_godot_version(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr("Godot Version:"));
+            let response = ui.text_edit_singleline(&mut self.godot_version);
+            match self.godot_version.parse::<GodotVersion>() {
+                // Normalized once the user is done typing, e.g. "v4.3" becomes "4.3"
+                Ok(version) if response.lost_focus() => self.godot_version = version.to_string(),
+                Ok(_) => {}
+                Err(err) => {
+                    ui.colored_label(egui::Color32::RED, err);
+                }
+            }
+        });
+    }
 
-fn show_creation_progress(ui: &mut egui::Ui) {
-    ui.horizontal(|ui| {
-        ui.spinner();
-    });
+    fn show_project_settings(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr("Display Name:"));
+            ui.add(egui::TextEdit::singleline(&mut self.display_name).hint_text(tr("Same as project name")));
+        });
+        // The hints show what an empty field falls back to
+        let derived = ProjectNames::new(&self.project_name, &ProjectOptions::default());
+        ui.horizontal(|ui| {
+            ui.label(tr("Crate Name:"));
+            ui.add(egui::TextEdit::singleline(&mut self.crate_name).hint_text(derived.crate_name.as_str()));
+        });
+        let derived = ProjectNames::new(
+            &self.project_name,
+            &ProjectOptions {
+                crate_name: self.crate_name.trim().to_string(),
+                ..Default::default()
+            },
+        );
+        ui.horizontal(|ui| {
+            ui.label(tr("Class Prefix:"));
+            ui.add(egui::TextEdit::singleline(&mut self.class_prefix).hint_text(derived.class_prefix.as_str()));
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("Project Type:"));
+            ui.radio_value(&mut self.flavor, ProjectFlavor::Game, tr("Game"));
+            ui.radio_value(&mut self.flavor, ProjectFlavor::Addon, tr("Editor addon (EditorPlugin class under addons/)"));
+        });
+        ui.add_enabled_ui(self.flavor == ProjectFlavor::Game, |ui| self.show_base_class(ui));
+        ui.horizontal(|ui| {
+            ui.label(tr("Renderer:"));
+            for renderer in [Renderer::ForwardPlus, Renderer::Mobile, Renderer::Compatibility] {
+                ui.radio_value(&mut self.renderer, renderer, renderer.to_string());
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.set_locale, tr("Fallback locale:"));
+            ui.add_enabled(self.set_locale, egui::TextEdit::singleline(&mut self.locale).hint_text("en"));
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("License:"));
+            egui::ComboBox::from_id_salt("license")
+                .selected_text(self.license.to_string())
+                .show_ui(ui, |ui| {
+                    for license in License::ALL {
+                        ui.selectable_value(&mut self.license, license, license.to_string());
+                    }
+                });
+            ui.add_enabled_ui(self.license != License::None, |ui| {
+                ui.label(tr("Author:"));
+                ui.text_edit_singleline(&mut self.author);
+            });
+        });
+        ui.horizontal(|ui| {
+            ui.label(tr("Rust Crate:"));
+            ui.radio_value(&mut self.crate_location, CrateLocation::Inside, tr("rust/ inside the project"));
+            ui.radio_value(
+                &mut self.crate_location,
+                CrateLocation::Sibling,
+                tr("<name>_rust/ next to the project (loaded via res://../)"),
+            );
+            ui.radio_value(&mut self.crate_location, CrateLocation::Root, tr("<name>/godot/ and <name>/rust/ side by side"));
+        });
+    }
+
+    fn show_base_class(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr("Base Class:"));
+            egui::ComboBox::from_id_salt("base_class")
+                .selected_text(self.base_class.to_string())
+                .show_ui(ui, |ui| {
+                    for base_class in BaseClass::ALL {
+                        ui.selectable_value(&mut self.base_class, base_class, base_class.to_string());
+                    }
+                });
+        });
+    }
+
+    fn show_reloadable_checkbox(&mut self, ui: &mut egui::Ui) {
+        ui.checkbox(&mut self.reloadable, tr("Reloadable"));
+    }
+
+    // The rest of the .gdextension's [configuration] section
+    fn show_gdextension_configuration(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr("Entry Symbol:"));
+            ui.add(egui::TextEdit::singleline(&mut self.entry_symbol).hint_text(DEFAULT_ENTRY_SYMBOL));
+        });
+        version_field(ui, tr("Max Godot Version:"), &mut self.max_godot_version, tr("No maximum"));
+        ui.horizontal(|ui| {
+            ui.label(tr("Extension Crates:"));
+            ui.add(egui::TextEdit::singleline(&mut self.extension_crates).hint_text(tr("e.g. editor_tools, networking")));
+        });
+    }
+
+    fn show_targets_group(&mut self, ui: &mut egui::Ui) {
+        // The paths the .gdextension gets when a field is left empty, shown as the fields' hints
+        let options = self.project_options();
+        let names = ProjectNames::new(&self.project_name, &options);
+        let crate_dir = crate_relative_dir(&self.project_name, options.crate_location);
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr("Targets:"));
+                match &self.host_triple {
+                    Some(host_triple) => ui.weak(tr_fmt("host {}", &[host_triple])),
+                    None if self.host_receiver.is_some() => ui.weak(tr("detecting host...")),
+                    None => ui.weak(tr("host unknown, rustc was not found")),
+                };
+            });
+            let columns = ((ui.available_width() / TARGET_COLUMN_WIDTH) as usize).clamp(1, self.targets.len().max(1));
+            let rows = self.targets.len().div_ceil(columns);
+            ui.columns(columns, |columns| {
+                // Filled top to bottom, so the targets read in order down each column
+                for (index, (target, is_selected)) in self.targets.iter_mut().enumerate() {
+                    let ui = &mut columns[index / rows];
+                    target_checkbox(ui, target, is_selected, self.host_triple.as_deref());
+                    if *is_selected {
+                        profile_field(
+                            ui,
+                            self.target_profiles.entry(target.clone()).or_default(),
+                            default_profile(target).unwrap_or_default(),
+                        );
+                        let default_path = default_library_path(&names, &crate_dir, &options, target).unwrap_or_default();
+                        library_path_field(ui, self.library_paths.entry(target.clone()).or_default(), default_path);
+                    }
+                }
+            });
+        });
+    }
+
+    fn show_godot_features_group(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.label(tr("godot crate features:"));
+            for (feature, is_selected) in &mut self.godot_features {
+                let label = if feature == "api-custom" {
+                    tr("api-custom (needs GODOT4_BIN set to the Godot build at compile time)").to_string()
+                } else {
+                    feature.clone()
+                };
+                ui.checkbox(is_selected, label);
+            }
+        });
+    }
+
+    fn show_dependencies_group(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.label(tr("Dependencies (newest version from crates.io when creating):"));
+            ui.horizontal_wrapped(|ui| {
+                for ((name, is_selected), (_, _, features)) in self.dependencies.iter_mut().zip(KNOWN_DEPENDENCIES) {
+                    let label = if features.is_empty() {
+                        name.clone()
+                    } else {
+                        format!("{} ({})", name, features.join(", "))
+                    };
+                    ui.checkbox(is_selected, label);
+                }
+            });
+        });
+    }
+
+    fn show_post_create_hooks(&mut self, ui: &mut egui::Ui) {
+        egui::CollapsingHeader::new(tr("Post-create commands"))
+            .id_salt("post_create_hooks")
+            .show(ui, |ui| {
+                ui.label(tr("Run in the new project's folder after creation, one shell command per line:"));
+                ui.add(
+                    egui::TextEdit::multiline(&mut self.post_create_hooks)
+                        .code_editor()
+                        .desired_rows(3)
+                        .desired_width(f32::INFINITY)
+                        .hint_text("cargo fmt --manifest-path {crate_dir}/Cargo.toml"),
+                );
+                let template_hooks = self.templates.as_ref().map_or(&[][..], |templates| templates.post_create_hooks.as_slice());
+                if !template_hooks.is_empty() {
+                    ui.checkbox(&mut self.template_hooks, tr("Also run the template set's commands first:"));
+                    for hook in template_hooks {
+                        ui.monospace(hook.as_str());
+                    }
+                }
+            });
+    }
+
+    fn show_git_init(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.git_init, tr("Initialize git repository"));
+            ui.add_enabled_ui(self.git_init, |ui| {
+                ui.label(tr("Commit message:"));
+                ui.text_edit_singleline(&mut self.git_commit_message);
+            });
+        });
+    }
+
+    fn show_existing_project(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr("If the project exists:"));
+            ui.radio_value(&mut self.existing_project, ExistingProject::Refuse, tr("Refuse"));
+            ui.radio_value(&mut self.existing_project, ExistingProject::Update, tr("Update changed files"));
+            ui.radio_value(&mut self.existing_project, ExistingProject::AddMissing, tr("Add missing files"));
+            ui.radio_value(&mut self.existing_project, ExistingProject::Overwrite, tr("Overwrite"));
+            ui.radio_value(&mut self.existing_project, ExistingProject::Retrofit, tr("Add Rust to a Godot project"))
+                .on_hover_text(tr(
+                    "Adds the crate, .gdextension and .gitignore entries, project.godot and scenes are left alone",
+                ));
+        });
+        if self.existing_project == ExistingProject::Retrofit {
+            ui.horizontal_wrapped(|ui| {
+                ui.label(tr("Godot projects here:"));
+                let projects = find_godot_projects();
+                if projects.is_empty() {
+                    ui.weak(tr("none, type the project's folder as the project name"));
+                }
+                for project in projects {
+                    if ui.selectable_label(self.project_name == project, project.as_str()).clicked() {
+                        self.project_name = project;
+                    }
+                }
+            });
+        }
+    }
+
+    fn show_classes(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label("lib.rs:");
+            ui.radio_value(&mut self.lib_scaffold, LibScaffold::Minimal, tr("Minimal class"));
+            ui.radio_value(
+                &mut self.lib_scaffold,
+                LibScaffold::Example,
+                tr("Example with a property, signal, functions and tests"),
+            );
+        });
+        ui.group(|ui| {
+            ui.label(tr("Additional classes:"));
+            ui.horizontal(|ui| {
+                ui.label(tr("Layout:"));
+                ui.radio_value(&mut self.layout, CrateLayout::Flat, tr("All in lib.rs"));
+                ui.radio_value(&mut self.layout, CrateLayout::Classes, tr("classes/ module per class"));
+                ui.radio_value(&mut self.layout, CrateLayout::Domains, tr("gameplay/, systems/, ui/ folders"));
+            });
+            let mut removed = None;
+            for (index, class) in self.classes.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.add(egui::TextEdit::singleline(&mut class.name).hint_text("ClassName"));
+                    egui::ComboBox::from_id_salt(("class_base", index))
+                        .selected_text(class.base_class.to_string())
+                        .show_ui(ui, |ui| {
+                            for base_class in BaseClass::ALL {
+                                ui.selectable_value(&mut class.base_class, base_class, base_class.to_string());
+                            }
+                        });
+                    if ui.button(tr("Remove")).clicked() {
+                        removed = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = removed {
+                self.classes.remove(index);
+            }
+
+            if ui.button(tr("Add class")).clicked() {
+                self.classes.push(ClassSpec::default());
+            }
+
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.class_list).hint_text("Player:CharacterBody2D, Enemy:Area2D, GameState:Resource"));
+                if ui.button(tr("Add from list")).clicked() {
+                    match parse_class_list(&self.class_list) {
+                        Ok(classes) => {
+                            self.classes.extend(classes);
+                            self.class_list.clear();
+                            self.class_list_error = None;
+                        }
+                        Err(err) => self.class_list_error = Some(err),
+                    }
+                }
+            });
+            if let Some(err) = &self.class_list_error {
+                ui.colored_label(egui::Color32::RED, err);
+            }
+        });
+    }
+
+    fn show_release_profile(&mut self, ui: &mut egui::Ui) {
+        let profile = &mut self.release_profile;
+        ui.horizontal_wrapped(|ui| {
+            ui.label(tr("Release profile:"));
+            profile_setting_combo(ui, "opt-level", &mut profile.opt_level, &OPT_LEVELS);
+            profile_setting_combo(ui, "lto", &mut profile.lto, &LTO_MODES);
+            profile_setting_combo(ui, "strip", &mut profile.strip, &STRIP_MODES);
+
+            let mut custom_units = profile.codegen_units.is_some();
+            let mut units = profile.codegen_units.unwrap_or(1);
+            ui.checkbox(&mut custom_units, "codegen-units");
+            ui.add_enabled(custom_units, egui::DragValue::new(&mut units).range(1..=256));
+            profile.codegen_units = custom_units.then_some(units);
+
+            ui.checkbox(&mut profile.panic_abort, "panic = \"abort\"");
+        });
+    }
+
+    fn show_signing(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.checkbox(&mut self.sign_libraries, tr("Sign libraries with minisign, key:"));
+            ui.text_edit_singleline(&mut self.minisign_key);
+        });
+    }
+
+    fn show_template_variables(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.label(tr_fmt(
+                "Template variables, next to the built-in {}:",
+                &[&format!("{{{}}}", BUILTIN_VARIABLES.join("}, {"))],
+            ));
+            let mut removed = None;
+            for (index, (name, value)) in self.template_variables.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(name);
+                    ui.label("=");
+                    ui.text_edit_singleline(value);
+                    if ui.button(tr("Remove")).clicked() {
+                        removed = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = removed {
+                self.template_variables.remove(index);
+            }
+            if ui.button(tr("Add template variable")).clicked() {
+                self.template_variables.push((String::new(), String::new()));
+            }
+        });
+    }
+
+    fn show_env_vars(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            ui.horizontal(|ui| {
+                ui.label(tr("Cargo executable:"));
+                ui.add(egui::TextEdit::singleline(&mut self.cargo).hint_text("cargo"));
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("Extra cargo build flags:"));
+                ui.add(egui::TextEdit::singleline(&mut self.cargo_flags).hint_text("--features my_feature -Zbuild-std"));
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("Godot executable:"));
+                ui.add(egui::TextEdit::singleline(&mut self.godot).hint_text(GODOT_EXECUTABLE));
+                if ui.button(tr("Detect")).clicked() {
+                    match detect_godot_executable() {
+                        Some(path) => self.godot = path,
+                        None => self.log.lock().unwrap().info("No Godot installation found, using godot from the PATH."),
+                    }
+                }
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("Code editor command:"));
+                ui.add(egui::TextEdit::singleline(&mut self.editor).hint_text(CODE_EDITOR));
+            });
+            ui.label(tr("Environment variables for cargo and godot:"));
+            let mut removed = None;
+            for (index, (key, value)) in self.env_vars.iter_mut().enumerate() {
+                ui.horizontal(|ui| {
+                    ui.text_edit_singleline(key);
+                    ui.label("=");
+                    ui.text_edit_singleline(value);
+                    if ui.button(tr("Remove")).clicked() {
+                        removed = Some(index);
+                    }
+                });
+            }
+            if let Some(index) = removed {
+                self.env_vars.remove(index);
+            }
+
+            ui.horizontal(|ui| {
+                if ui.button(tr("Add variable")).clicked() {
+                    self.env_vars.push((String::new(), String::new()));
+                }
+                let exists = !self.project_name.is_empty() && project_exists(&self.project_name);
+                if ui.add_enabled(exists, egui::Button::new(tr("Load from project"))).clicked() {
+                    self.load_env_vars();
+                }
+                if ui.add_enabled(exists, egui::Button::new(tr("Save to project"))).clicked() {
+                    self.save_env_vars();
+                }
+            });
+        });
+    }
+
+    fn load_env_vars(&mut self) {
+        match ProjectMetadata::load(&find_project_dir(&self.project_name)) {
+            Ok(metadata) => {
+                self.env_vars = metadata.env.into_iter().collect();
+                self.cargo = metadata.cargo;
+                self.cargo_flags = metadata.cargo_args.join(" ");
+                self.godot = metadata.godot;
+                self.editor = metadata.editor;
+            }
+            Err(err) => self.log.lock().unwrap().error(&err.to_string()),
+        }
+    }
+
+    // Only the build settings change, the file policies in the metadata file are kept
+    fn save_env_vars(&mut self) {
+        let project_dir = find_project_dir(&self.project_name);
+        let options = self.project_options();
+        let saved = ProjectMetadata::update(&project_dir, |metadata| {
+            metadata.env = options.env;
+            metadata.cargo = options.cargo;
+            metadata.cargo_args = options.cargo_args;
+            metadata.godot = options.godot;
+            metadata.editor = options.editor;
+        });
+
+        let mut log_inner = self.log.lock().unwrap();
+        match saved {
+            Ok(()) => log_inner.info(&format!("Saved build settings to {}.", self.project_name)),
+            Err(err) => log_inner.error(&err.to_string()),
+        }
+    }
+
+    fn show_add_class(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        ui.group(|ui| {
+            ui.label(tr("Add class to existing project:"));
+            ui.horizontal(|ui| {
+                ui.add(egui::TextEdit::singleline(&mut self.new_class.name).hint_text("ClassName"));
+                egui::ComboBox::from_id_salt("new_class_base")
+                    .selected_text(self.new_class.base_class.to_string())
+                    .show_ui(ui, |ui| {
+                        for base_class in BaseClass::ALL {
+                            ui.selectable_value(&mut self.new_class.base_class, base_class, base_class.to_string());
+                        }
+                    });
+            });
+            ui.horizontal(|ui| {
+                ui.label(tr("Editor icon (optional):"));
+                ui.add(egui::TextEdit::singleline(&mut self.new_class_icon).hint_text("res://icon.svg"));
+            });
+
+            let exists = !self.project_name.is_empty() && project_exists(&self.project_name);
+            if ui.add_enabled(exists, egui::Button::new(tr("Add class"))).clicked() {
+                self.start_add_class(ctx);
+            }
+        });
+    }
+
+    fn show_reload_stress_test(&mut self, ui: &mut egui::Ui, ctx: &egui::Context) {
+        ui.horizontal(|ui| {
+            ui.label(tr("Reload stress test, iterations:"));
+            ui.add(egui::DragValue::new(&mut self.reload_iterations).range(1..=100));
+            if ui.button(tr("Run")).clicked() {
+                self.start_reload_stress_test(ctx);
+            }
+        });
+    }
+
+    fn show_project_overwrite_prompt(&mut self, ctx: &egui::Context) {
+        if !self.confirm_project_overwrite {
+            return;
+        }
+
+        egui::Window::new(tr("Overwrite project"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(tr_fmt(
+                    "Every generated file in '{}' will be replaced, including ones you edited. Continue?",
+                    &[&self.project_name],
+                ));
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Overwrite")).clicked() {
+                        self.confirm_project_overwrite = false;
+                        self.start_creation(ctx);
+                    }
+                    if ui.button(tr("Cancel")).clicked() {
+                        self.confirm_project_overwrite = false;
+                    }
+                });
+            });
+    }
+
+    // Closing mid-task would leave cargo running and a half-written project behind, so the close waits for a confirmed cancel
+    fn handle_close_request(&mut self, ctx: &egui::Context) {
+        let is_running = self.state == CreationState::Running;
+        let close_requested = ctx.input(|input| input.viewport().close_requested());
+        if let Some(started) = self.shutdown_started {
+            if !is_running || started.elapsed() >= SHUTDOWN_TIMEOUT {
+                self.save_settings(ctx);
+                ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+            }
+        } else if self.confirm_exit && !is_running {
+            // The task finished while the question was open, nothing is left to stop
+            self.save_settings(ctx);
+            ctx.send_viewport_cmd(egui::ViewportCommand::Close);
+        } else if is_running && close_requested {
+            ctx.send_viewport_cmd(egui::ViewportCommand::CancelClose);
+            self.confirm_exit = true;
+        } else if close_requested {
+            self.save_settings(ctx);
+        }
+    }
+
+    // The window's own choices, presets and recent projects are kept as saved since another window may have changed them
+    fn save_settings(&mut self, ctx: &egui::Context) {
+        let window = AppSettings {
+            godot_version: self.godot_version.trim().to_string(),
+            targets: self
+                .targets
+                .iter()
+                .filter_map(|(target, is_selected)| if *is_selected { Some(target.clone()) } else { None })
+                .collect(),
+            reloadable: self.reloadable,
+            precompile_lib: self.precompile_lib,
+            window_position: ctx
+                .input(|input| input.viewport().outer_rect)
+                .map(|rect| [rect.left_top().x, rect.left_top().y]),
+            theme: self.theme,
+            language: self.language,
+            check_for_updates: self.check_for_updates,
+            dismissed_release: self.dismissed_release.clone(),
+            post_create_hooks: self.project_options().post_create_hooks,
+            template_source: self.template_source.trim().to_string(),
+            ..AppSettings::default()
+        };
+        self.update_settings(|settings| {
+            *settings = AppSettings {
+                presets: std::mem::take(&mut settings.presets),
+                recent_projects: std::mem::take(&mut settings.recent_projects),
+                ..window
+            }
+        });
+    }
+
+    // Picks up what other windows saved too, a failure on close goes to a log nobody sees anymore
+    fn update_settings(&mut self, change: impl FnOnce(&mut AppSettings)) {
+        match AppSettings::update(change) {
+            Ok(settings) => {
+                self.presets = settings.presets;
+                self.recent_projects = settings.recent_projects;
+            }
+            Err(err) => self.log.lock().unwrap().error(&format!("Saving the settings failed: {}", err)),
+        }
+    }
+
+    fn show_presets(&mut self, ui: &mut egui::Ui) {
+        ui.horizontal(|ui| {
+            ui.label(tr("Preset:"));
+            let mut selected = None;
+            egui::ComboBox::from_id_salt("preset")
+                .selected_text(if self.presets.contains_key(&self.preset_name) {
+                    self.preset_name.as_str()
+                } else {
+                    tr("None")
+                })
+                .show_ui(ui, |ui| {
+                    for (name, preset) in &self.presets {
+                        if ui.selectable_label(*name == self.preset_name, name.as_str()).clicked() {
+                            selected = Some((name.clone(), preset.clone()));
+                        }
+                    }
+                });
+            if let Some((name, preset)) = selected {
+                self.preset_name = name;
+                self.apply_preset(preset);
+            }
+
+            ui.add(
+                egui::TextEdit::singleline(&mut self.preset_name)
+                    .hint_text(tr("Preset name"))
+                    .desired_width(PRESET_NAME_WIDTH),
+            );
+            let name = self.preset_name.trim().to_string();
+            // Written right away rather than on close, so a preset survives the window being killed
+            if ui.add_enabled(!name.is_empty(), egui::Button::new(tr("Save"))).clicked() {
+                let preset = self.current_preset();
+                self.update_settings(|settings| {
+                    settings.presets.insert(name.clone(), preset);
+                });
+            }
+            if ui.add_enabled(self.presets.contains_key(&name), egui::Button::new(tr("Delete"))).clicked() {
+                self.update_settings(|settings| {
+                    settings.presets.remove(&name);
+                });
+            }
+        });
+    }
+
+    fn current_preset(&self) -> Preset {
+        let options = self.project_options();
+        Preset {
+            template_source: self.template_source.trim().to_string(),
+            godot_version: self.godot_version.trim().to_string(),
+            reloadable: options.reloadable,
+            targets: options.targets,
+            godot_features: options.godot_features,
+            dependencies: options.dependencies,
+            flavor: options.flavor,
+            base_class: options.base_class,
+            layout: options.layout,
+            lib_scaffold: options.lib_scaffold,
+            crate_location: options.crate_location,
+            library_layout: options.library_layout,
+            task_runner: options.task_runner,
+            minimal_size: options.minimal_size,
+            release_profile: options.release_profile,
+            target_profiles: options.target_profiles,
+            double_precision: options.double_precision,
+            prelude_module: options.prelude_module,
+            code_style: options.code_style,
+            itest: options.itest,
+            class_icons: options.class_icons,
+            editor_plugin: options.editor_plugin,
+            readme: options.readme,
+            github_workflow: options.github_workflow,
+            export_presets: options.export_presets,
+            vscode: options.vscode,
+        }
+    }
+
+    fn apply_preset(&mut self, preset: Preset) {
+        if preset.template_source != self.template_source.trim() {
+            self.template_source = preset.template_source;
+            self.load_templates(false);
+        }
+        self.godot_version = preset.godot_version;
+        self.reloadable = preset.reloadable;
+        for (target, is_selected) in &mut self.targets {
+            *is_selected = preset.targets.contains(target);
+        }
+        for (feature, is_selected) in &mut self.godot_features {
+            *is_selected = preset.godot_features.contains(feature);
+        }
+        for (name, is_selected) in &mut self.dependencies {
+            *is_selected = preset.dependencies.contains(name);
+        }
+        self.flavor = preset.flavor;
+        self.base_class = preset.base_class;
+        self.layout = preset.layout;
+        self.lib_scaffold = preset.lib_scaffold;
+        self.crate_location = preset.crate_location;
+        self.library_layout = preset.library_layout;
+        self.task_runner = preset.task_runner;
+        self.minimal_size = preset.minimal_size;
+        self.release_profile = preset.release_profile;
+        self.target_profiles = preset.target_profiles;
+        self.double_precision = preset.double_precision;
+        self.prelude_module = preset.prelude_module;
+        self.code_style = preset.code_style;
+        self.itest = preset.itest;
+        self.class_icons = preset.class_icons;
+        self.editor_plugin = preset.editor_plugin;
+        self.readme = preset.readme;
+        self.github_workflow = preset.github_workflow;
+        self.export_presets = preset.export_presets;
+        self.vscode = preset.vscode;
+    }
+
+    fn add_recent_project(&mut self, project: RecentProject) {
+        self.update_settings(|settings| settings.add_recent_project(project));
+    }
+
+    fn show_recent_projects(&mut self, ui: &mut egui::Ui) {
+        if self.recent_projects.is_empty() {
+            return;
+        }
+
+        let mut clicked = None;
+        egui::CollapsingHeader::new(tr_fmt("Recent projects ({})", &[&self.recent_projects.len()]))
+            .id_salt("recent_projects")
+            .show(ui, |ui| {
+                for (index, recent) in self.recent_projects.iter().enumerate() {
+                    let exists = Path::new(&recent.path).is_dir();
+                    ui.horizontal(|ui| {
+                        ui.strong(recent.name.as_str()).on_hover_text(recent.path.as_str());
+                        if ui.add_enabled(exists, egui::Button::new(tr("Open folder")).small()).clicked() {
+                            clicked = Some((index, RecentAction::OpenFolder));
+                        }
+                        if ui.add_enabled(exists, egui::Button::new(tr("Open in Godot")).small()).clicked() {
+                            clicked = Some((index, RecentAction::OpenInGodot));
+                        }
+                        if ui.small_button(tr("Re-create with same settings")).clicked() {
+                            clicked = Some((index, RecentAction::Recreate));
+                        }
+                        if ui.small_button(tr("Forget")).clicked() {
+                            clicked = Some((index, RecentAction::Forget));
+                        }
+                        if !exists {
+                            ui.label(tr("(folder is gone)"));
+                        }
+                    });
+                }
+            });
+
+        let Some((index, action)) = clicked else {
+            return;
+        };
+        let recent = self.recent_projects[index].clone();
+        match action {
+            RecentAction::OpenFolder => {
+                if let Err(err) = open_folder(Path::new(&recent.path)) {
+                    self.state = CreationState::Failed(tr_fmt("Opening the folder failed: {}", &[&err]));
+                }
+            }
+            RecentAction::OpenInGodot => {
+                if let Err(err) = open_in_editor(&recent.path) {
+                    self.state = CreationState::Failed(tr_fmt("Opening Godot failed: {}", &[&err]));
+                }
+            }
+            // Only fills in the form, creating still goes through the usual checks and overwrite prompt
+            RecentAction::Recreate => {
+                self.project_name = recent.name;
+                self.apply_preset(recent.preset);
+            }
+            // By path, the list may have changed under the index when another window saved
+            RecentAction::Forget => self.update_settings(|settings| settings.recent_projects.retain(|other| other.path != recent.path)),
+        }
+    }
+
+    fn cancel_task(&mut self) {
+        self.cancel.store(true, Ordering::Relaxed);
+        // A task waiting on a question gets a "no" once the prompt is dropped
+        self.prompt = None;
+    }
+
+    // Esc answers an open question with "no" or stops the running task, Ctrl+L clears the log and
+    // Ctrl+O opens the newest recent project's folder, Ctrl being Cmd on macOS
+    fn handle_shortcuts(&mut self, ctx: &egui::Context) {
+        let (escape, clear_log, open_recent) = ctx.input_mut(|input| {
+            (
+                input.consume_key(egui::Modifiers::NONE, egui::Key::Escape),
+                input.consume_key(egui::Modifiers::COMMAND, egui::Key::L),
+                input.consume_key(egui::Modifiers::COMMAND, egui::Key::O),
+            )
+        });
+
+        if escape {
+            if let Some(prompt) = self.prompt.take() {
+                let _ = prompt.reply.send(false);
+            } else if self.confirm_project_overwrite {
+                self.confirm_project_overwrite = false;
+            } else if self.state == CreationState::Running {
+                self.cancel_task();
+            }
+        }
+        if clear_log {
+            self.log.lock().unwrap().clear();
+        }
+        if open_recent {
+            if let Some(recent) = self.recent_projects.first() {
+                if let Err(err) = open_folder(Path::new(&recent.path)) {
+                    self.state = CreationState::Failed(tr_fmt("Opening the folder failed: {}", &[&err]));
+                }
+            }
+        }
+    }
+
+    fn show_exit_prompt(&mut self, ctx: &egui::Context) {
+        if !self.confirm_exit {
+            return;
+        }
+
+        egui::Window::new(tr("Quit"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                if self.shutdown_started.is_some() {
+                    ui.horizontal(|ui| {
+                        ui.spinner();
+                        ui.label(tr("Stopping the running task..."));
+                    });
+                    return;
+                }
+                ui.label(tr(
+                    "A task is still running. Quitting stops it, kills the processes it started and rolls back what it created.",
+                ));
+                ui.horizontal(|ui| {
+                    if ui.button(tr("Stop and quit")).clicked() {
+                        self.cancel_task();
+                        self.shutdown_started = Some(Instant::now());
+                    }
+                    if ui.button(tr("Keep running")).clicked() {
+                        self.confirm_exit = false;
+                    }
+                });
+            });
+    }
+
+    fn show_prompt(&mut self, ctx: &egui::Context) {
+        let Some(prompt) = &self.prompt else {
+            return;
+        };
+
+        let mut answer = None;
+        egui::Window::new(prompt.title)
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.label(prompt.message.as_str());
+                ui.horizontal(|ui| {
+                    if ui.button(prompt.accept).clicked() {
+                        answer = Some(true);
+                    }
+                    if ui.button(prompt.reject).clicked() {
+                        answer = Some(false);
+                    }
+                });
+            });
+
+        if let Some(answer) = answer {
+            if let Some(prompt) = self.prompt.take() {
+                let _ = prompt.reply.send(answer);
+            }
+        }
+    }
+
+    fn render_preview(&self) -> Result<Vec<(String, String)>, String> {
+        let templates = self
+            .templates
+            .as_ref()
+            .ok_or(CreateError::TemplatesUnavailable)
+            .map_err(|err| err.to_string())?;
+        render_project_files(&self.project_name, templates, &self.project_options()).map_err(|err| err.to_string())
+    }
+
+    fn show_preview(&mut self, ctx: &egui::Context) {
+        if !self.preview_open {
+            return;
+        }
+
+        // Rendered every frame so the preview follows the name, version and target inputs as they change
+        let preview = self.render_preview();
+        let mut open = true;
+        egui::Window::new(tr("Preview"))
+            .id(egui::Id::new("preview"))
+            .open(&mut open)
+            .default_width(LOG_TEXT_WIDTH)
+            .show(ctx, |ui| match preview {
+                Err(err) => {
+                    ui.colored_label(egui::Color32::RED, err);
+                }
+                Ok(files) => {
+                    let paths: Vec<&str> = files.iter().map(|(path, _)| path.as_str()).collect();
+                    let selected = paths.iter().position(|path| *path == self.preview_selected).unwrap_or(0);
+                    let root = if self.project_name.is_empty() {
+                        tr("<project name>")
+                    } else {
+                        &self.project_name
+                    };
+
+                    egui::ScrollArea::vertical()
+                        .id_salt("preview_tree")
+                        .max_height(PREVIEW_TREE_HEIGHT)
+                        .show(ui, |ui| {
+                            for (line, index) in format_file_tree(root, &paths) {
+                                match index {
+                                    Some(index) => {
+                                        if ui.selectable_label(selected == index, egui::RichText::new(line).monospace()).clicked() {
+                                            self.preview_selected = paths[index].to_string();
+                                        }
+                                    }
+                                    None => {
+                                        ui.monospace(line);
+                                    }
+                                }
+                            }
+                        });
+
+                    ui.separator();
+                    if let Some((_, content)) = files.get(selected) {
+                        egui::ScrollArea::both()
+                            .id_salt("preview_content")
+                            .max_height(PREVIEW_CONTENT_HEIGHT)
+                            .show(ui, |ui| {
+                                ui.monospace(content.as_str());
+                            });
+                    }
+                }
+            });
+
+        if !open {
+            self.preview_open = false;
+        }
+    }
+
+    fn show_template_editor(&mut self, ctx: &egui::Context) {
+        if !self.template_editor_open {
+            return;
+        }
+
+        let path = self.local_template_path();
+        let mut open = true;
+        let mut save = false;
+        let mut revert = false;
+        egui::Window::new(tr("Template Editor"))
+            .id(egui::Id::new("template_editor"))
+            .open(&mut open)
+            .default_width(LOG_TEXT_WIDTH)
+            .show(ctx, |ui| {
+                let Some(templates) = &mut self.templates else {
+                    ui.label(tr("The templates aren't loaded."));
+                    return;
+                };
+                ui.horizontal(|ui| {
+                    for field in EDITABLE_TEMPLATES {
+                        ui.selectable_value(&mut self.template_editor_field, field, field);
+                    }
+                });
+                if let Some(template) = templates.editable_mut(self.template_editor_field) {
+                    egui::ScrollArea::both()
+                        .id_salt("template_editor")
+                        .max_height(TEMPLATE_EDITOR_HEIGHT)
+                        .show(ui, |ui| {
+                            let editor = egui::TextEdit::multiline(template).code_editor().desired_width(f32::INFINITY);
+                            if ui.add(editor).changed() {
+                                self.template_editor_edited.insert(self.template_editor_field);
+                            }
+                        });
+                }
+                let edited = !self.template_editor_edited.is_empty();
+                ui.horizontal(|ui| {
+                    let target = path.as_deref().unwrap_or(TEMPLATE_FILE);
+                    save = ui
+                        .add_enabled(edited && path.is_some(), egui::Button::new(tr_fmt("Save to {}", &[&target])))
+                        .on_disabled_hover_text(tr(
+                            "Remote templates can't be saved, copy them to a local file and use that as the template source.",
+                        ))
+                        .clicked();
+                    revert = ui.add_enabled(edited, egui::Button::new(tr("Revert"))).clicked();
+                    if edited {
+                        ui.label(tr("Unsaved changes, the preview already uses them."));
+                    }
+                });
+            });
+
+        if save {
+            self.save_template_edits(path.as_deref().unwrap_or(TEMPLATE_FILE));
+        }
+        if revert {
+            self.load_templates(false);
+        }
+        if !open {
+            self.template_editor_open = false;
+        }
+    }
+
+    fn show_gdextension_editor(&mut self, ctx: &egui::Context) {
+        if !self.gdextension_editor_open {
+            return;
+        }
+
+        // A target ticked without a path gets the path creating this project would give it
+        let options = self.project_options();
+        let names = ProjectNames::new(&self.project_name, &options);
+        let crate_dir = crate_relative_dir(&self.project_name, options.crate_location);
+        let default_path = |target: &str| default_library_path(&names, &crate_dir, &options, target);
+
+        let mut open = true;
+        let mut reopen = false;
+        let mut save = false;
+        let editor = &mut self.gdextension_editor;
+        egui::Window::new(tr(".gdextension Editor"))
+            .id(egui::Id::new("gdextension_editor"))
+            .open(&mut open)
+            .default_width(LOG_TEXT_WIDTH)
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("File:"));
+                    ui.add(egui::TextEdit::singleline(&mut editor.path).hint_text("my_project/my_project.gdextension"));
+                    reopen = ui.add_enabled(!editor.path.trim().is_empty(), egui::Button::new(tr("Open"))).clicked();
+                });
+                let Some((opened, _)) = &editor.file else {
+                    return;
+                };
+                ui.weak(tr_fmt("Editing {}", &[opened]));
+                ui.horizontal(|ui| {
+                    ui.label(tr("Entry Symbol:"));
+                    ui.add(egui::TextEdit::singleline(&mut editor.entry_symbol).hint_text(DEFAULT_ENTRY_SYMBOL));
+                });
+                version_field(
+                    ui,
+                    tr("Min Godot Version:"),
+                    &mut editor.compatibility_minimum,
+                    &DEFAULT_GODOT_VERSION.to_string(),
+                );
+                version_field(ui, tr("Max Godot Version:"), &mut editor.compatibility_maximum, tr("No maximum"));
+                ui.checkbox(&mut editor.reloadable, tr("Reloadable (hot reload when the library is rebuilt)"));
+                ui.group(|ui| {
+                    ui.label(tr("Libraries:"));
+                    for (target, is_selected, path) in &mut editor.libraries {
+                        target_checkbox(ui, target, is_selected, self.host_triple.as_deref());
+                        if *is_selected {
+                            library_path_field(ui, path, default_path(target).unwrap_or_default());
+                        }
+                    }
+                });
+                let problems = editor.problems();
+                ui.horizontal(|ui| {
+                    save = ui
+                        .add_enabled(problems.is_empty(), egui::Button::new(tr("Save")))
+                        .on_disabled_hover_text(problems.join("\n"))
+                        .clicked();
+                    if ui.button(tr("Revert")).clicked() {
+                        editor.path = opened.clone();
+                        reopen = true;
+                    }
+                });
+            });
+
+        if reopen {
+            match GdExtensionEditor::open(self.gdextension_editor.path.trim()) {
+                Ok(editor) => self.gdextension_editor = editor,
+                Err(err) => self.log.lock().unwrap().error(&err),
+            }
+        }
+        if save {
+            match self.gdextension_editor.save(&default_path) {
+                Ok(path) => self.log.lock().unwrap().info(&format!("Saved {}.", path)),
+                Err(err) => self.log.lock().unwrap().error(&err),
+            }
+        }
+        if !open {
+            self.gdextension_editor_open = false;
+        }
+    }
+
+    // Only the edited templates are written back, then the file is loaded again to check it still parses
+    fn save_template_edits(&mut self, path: &str) {
+        let Some(templates) = &mut self.templates else {
+            return;
+        };
+        let saved = self.template_editor_edited.iter().try_for_each(|field| match templates.editable_mut(field) {
+            Some(template) => save_template(path, field, template),
+            None => Ok(()),
+        });
+        match saved {
+            Ok(()) => {
+                self.log.lock().unwrap().info(&format!("Saved the templates to {}.", path));
+                self.load_templates(false);
+            }
+            Err(err) => self.log.lock().unwrap().error(&err),
+        }
+    }
+
+    fn show_log(&mut self, ui: &mut egui::Ui) {
+        ui.group(|ui| {
+            let log = self.log.lock().unwrap();
+            let shown: Vec<&LogEntry> = log.entries().iter().filter(|entry| entry.level >= self.log_level).collect();
+            ui.horizontal(|ui| {
+                ui.label(tr("Log:"));
+                egui::ComboBox::from_id_salt("log_level")
+                    .selected_text(log_level_label(self.log_level))
+                    .show_ui(ui, |ui| {
+                        for level in LogLevel::ALL {
+                            ui.selectable_value(&mut self.log_level, level, log_level_label(level));
+                        }
+                    });
+            });
+
+            if shown.is_empty() {
+                ui.weak(tr(PROJECT_NAME_HINT));
+            } else {
+                // Only the visible rows are laid out, a cargo build easily logs thousands of lines
+                let row_height = ui.text_style_height(&egui::TextStyle::Monospace);
+                let height = (ui.available_height() - LOG_FOOTER_HEIGHT).max(LOG_MIN_HEIGHT);
+                egui::ScrollArea::both()
+                    .max_height(height)
+                    .auto_shrink([false, true])
+                    .stick_to_bottom(true)
+                    .show_rows(ui, row_height, shown.len(), |ui, rows| {
+                        for entry in &shown[rows] {
+                            let text = egui::RichText::new(format_log_entry(entry)).monospace();
+                            let text = match entry.level {
+                                LogLevel::Output => text.weak(),
+                                LogLevel::Info => text,
+                                LogLevel::Warning => text.color(egui::Color32::YELLOW),
+                                LogLevel::Error => text.color(egui::Color32::RED),
+                            };
+                            ui.label(text);
+                        }
+                    });
+            }
+
+            let has_entries = !log.entries().is_empty();
+            ui.horizontal(|ui| {
+                if ui.add_enabled(has_entries, egui::Button::new(tr("Copy log"))).clicked() {
+                    ui.ctx().copy_text(log.text());
+                }
+                if ui.add_enabled(has_entries, egui::Button::new(tr("Save log to file…"))).clicked() {
+                    self.log_save_path = Some(LOG_FILE.to_string());
+                }
+            });
+        });
+    }
+
+    fn show_log_save_prompt(&mut self, ctx: &egui::Context) {
+        let Some(path) = &mut self.log_save_path else {
+            return;
+        };
+
+        let mut save = false;
+        let mut cancel = false;
+        egui::Window::new(tr("Save log"))
+            .collapsible(false)
+            .resizable(false)
+            .anchor(egui::Align2::CENTER_CENTER, [0.0, 0.0])
+            .show(ctx, |ui| {
+                ui.horizontal(|ui| {
+                    ui.label(tr("File:"));
+                    ui.add(egui::TextEdit::singleline(path).hint_text(tr("relative to the working directory")));
+                });
+                ui.horizontal(|ui| {
+                    save = ui.add_enabled(!path.trim().is_empty(), egui::Button::new(tr("Save"))).clicked();
+                    cancel = ui.button(tr("Cancel")).clicked();
+                });
+            });
+
+        if save {
+            let path = path.trim().to_string();
+            let mut log = self.log.lock().unwrap();
+            let text = log.text();
+            match write_atomic(Path::new(&path), &text) {
+                Ok(()) => log.info(&format!("Saved the log to {}.", path)),
+                Err(err) => log.error(&format!("Failed to save the log to {}: {}", path, err)),
+            }
+        }
+        if save || cancel {
+            self.log_save_path = None;
+        }
+    }
+}
+
+// The questions creation can ask, the GUI answers them through the prompt window
+struct CreationPrompts<'a> {
+    overwrite: &'a dyn Fn(&str) -> bool,
+    collisions: &'a dyn Fn(&[String]) -> bool,
+    rust_targets: &'a dyn Fn(&[String]) -> bool,
+}
+
+fn handle_create_project(
+    project_name: &str,
+    log_clone: Arc<Mutex<Log>>,
+    templates: Option<&ProjectTemplates>,
+    options: &ProjectOptions,
+    cancel: &AtomicBool,
+    prompts: &CreationPrompts,
+    builds: &Mutex<Vec<TargetBuild>>,
+) -> Result<(), CreateError> {
+    if project_name.is_empty() {
+        return Err(CreateError::EmptyName);
+    }
+
+    if (project_exists(project_name) || sibling_crate_exists(project_name, options)) && options.existing_project == ExistingProject::Refuse {
+        return Err(CreateError::AlreadyExists);
+    }
+
+    log_clone.lock().unwrap().info("Checking names for collisions...");
+    let collisions = name_collisions(&ProjectNames::new(project_name, options), options, cancel);
+    if cancel.load(Ordering::Relaxed) || (!collisions.is_empty() && !(prompts.collisions)(&collisions)) {
+        return Err(CreateError::Cancelled);
+    }
+
+    {
+        let mut log_inner = log_clone.lock().unwrap();
+        log_inner.info("Creating project...");
+    }
+
+    // Call the actual function to create the project
+    let templates = match templates {
+        Some(templates) => templates,
+        None => return Err(CreateError::TemplatesUnavailable),
+    };
+
+    if options.precompile_lib {
+        offer_rust_targets(&log_clone, options, cancel, prompts.rust_targets)?;
+    }
+
+    create_project(project_name, log_clone, templates, options, cancel, prompts.overwrite, builds)?;
+
+    Ok(())
+}
+
+// Declining the install still builds, the targets rustup lacks fail on their own
+fn offer_rust_targets(
+    log: &Arc<Mutex<Log>>,
+    options: &ProjectOptions,
+    cancel: &AtomicBool,
+    confirm_rust_targets: &dyn Fn(&[String]) -> bool,
+) -> Result<(), CreateError> {
+    let missing = missing_rust_targets(options);
+    if !missing.is_empty() && confirm_rust_targets(&missing) {
+        install_rust_targets(&missing, log, cancel)?;
+    }
+    Ok(())
+}
+
+fn confirm_rust_targets(asker: &Asker, triples: &[String]) -> bool {
+    let message = tr_fmt(
+        "The selected targets need rustup targets that aren't installed:\n{}\nRun rustup target add for them now?",
+        &[&triples.join("\n")],
+    );
+    asker.ask(tr("Missing rustup targets"), message, tr("Install"), tr("Build without them"))
+}
+
+// None is cargo's default for the setting, which leaves it out of Cargo.toml
+fn profile_setting_combo(ui: &mut egui::Ui, key: &str, value: &mut Option<String>, choices: &[&str]) {
+    ui.label(format!("{}:", key));
+    egui::ComboBox::from_id_salt(key)
+        .selected_text(value.as_deref().unwrap_or(tr("default")))
+        .show_ui(ui, |ui| {
+            ui.selectable_value(value, None, tr("default"));
+            for choice in choices {
+                ui.selectable_value(value, Some(choice.to_string()), *choice);
+            }
+        });
+}
+
+// An optional Godot version, tidied up like "4.3.0" -> "4.3" once the field loses focus
+fn version_field(ui: &mut egui::Ui, label: &str, text: &mut String, hint: &str) {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        let response = ui.add(egui::TextEdit::singleline(text).hint_text(hint));
+        if text.trim().is_empty() {
+            return;
+        }
+        match text.parse::<GodotVersion>() {
+            Ok(version) if response.lost_focus() => *text = version.to_string(),
+            Ok(_) => {}
+            Err(err) => {
+                ui.colored_label(egui::Color32::RED, err);
+            }
+        }
+    });
+}
+
+// With whether the host toolchain builds the target as is, once the host is known
+fn target_checkbox(ui: &mut egui::Ui, target: &str, is_selected: &mut bool, host_triple: Option<&str>) {
+    ui.horizontal(|ui| {
+        ui.checkbox(is_selected, target);
+        let Some(host_triple) = host_triple else {
+            return;
+        };
+        if is_native_target(target, host_triple) {
+            ui.weak(tr("native")).on_hover_text(tr_fmt("Built by the {} toolchain as is.", &[&host_triple]));
+        } else {
+            ui.colored_label(egui::Color32::YELLOW, tr("⚠ cross")).on_hover_text(tr(
+                "Cross build: needs the matching rustup target and a linker for that platform, or a build on the platform itself.",
+            ));
+        }
+    });
+}
+
+// Empty builds with the debug or release profile the target's name asks for, shown as the hint
+fn profile_field(ui: &mut egui::Ui, profile: &mut String, hint: &str) {
+    ui.horizontal(|ui| {
+        ui.add_space(LIBRARY_PATH_INDENT);
+        ui.label(tr("Cargo profile:"));
+        ui.add(egui::TextEdit::singleline(profile).hint_text(hint).desired_width(PROFILE_NAME_WIDTH));
+    });
+}
+
+// Empty uses the hint, the path the target's library gets by default
+fn library_path_field(ui: &mut egui::Ui, path: &mut String, hint: String) {
+    ui.horizontal(|ui| {
+        ui.add_space(LIBRARY_PATH_INDENT);
+        ui.add(egui::TextEdit::singleline(path).hint_text(hint).desired_width(ui.available_width()));
+    });
+}
+
+fn show_creation_progress(ui: &mut egui::Ui) {
+    ui.horizontal(|ui| {
+        ui.spinner();
+    });
+}
+
+// Checklist after a successful creation, worded for the options the project was created with
+fn quick_start_steps(project_name: &str, options: &ProjectOptions) -> Vec<QuickStartStep> {
+    let crate_path = crate_path(project_name, options.crate_location);
+    let build_command = options.cargo_build_command(&crate_path, "debug");
+    let mut build_text = tr_fmt("Build the library with `{} build` in {}", &[&options.cargo_program(), &crate_path]);
+    if options.library_layout == LibraryLayout::Bin {
+        build_text.push_str(tr(", then run copy_libs to fill bin/"));
+    }
+
+    let class_name = main_class_name(&ProjectNames::new(project_name, options));
+    let base_class = options.main_base_class();
+    let mut class_text = match base_class {
+        BaseClass::EditorPlugin => tr_fmt("{} is a tool class, the editor runs it as soon as the extension loads", &[&class_name]),
+        BaseClass::Resource => tr_fmt("Create a {} from the New Resource dialog of the FileSystem dock", &[&class_name]),
+        BaseClass::RefCounted => tr_fmt("Instantiate {} from GDScript with {}.new()", &[&class_name, &class_name]),
+        _ => tr_fmt(
+            "{} is the root of main.tscn, add more from the Create Node dialog under {}",
+            &[&class_name, &base_class],
+        ),
+    };
+    if !options.classes.is_empty() {
+        class_text.push_str(&tr_fmt(", {} more classes register alongside it", &[&options.classes.len()]));
+    }
+
+    let reload_text = if options.reloadable {
+        tr("Rebuild while the editor is open, Godot hot reloads the library when it regains focus").to_string()
+    } else {
+        tr("Restart the editor after rebuilding, the extension isn't reloadable").to_string()
+    };
+
+    vec![
+        QuickStartStep {
+            text: build_text,
+            actions: vec![QuickStartAction::Copy(build_command), QuickStartAction::Build, QuickStartAction::OpenInEditor],
+        },
+        QuickStartStep {
+            text: tr_fmt("Open the project in Godot {}", &[&options.godot_version]),
+            actions: vec![QuickStartAction::OpenInGodot],
+        },
+        QuickStartStep {
+            text: class_text,
+            actions: Vec::new(),
+        },
+        QuickStartStep {
+            text: reload_text,
+            actions: Vec::new(),
+        },
+    ]
+}
+
+fn log_level_label(level: LogLevel) -> &'static str {
+    match level {
+        LogLevel::Output => tr("Everything"),
+        LogLevel::Info => tr("Steps, warnings and errors"),
+        LogLevel::Warning => tr("Warnings and errors"),
+        LogLevel::Error => tr("Errors only"),
+    }
+}
+
+fn format_log_entry(entry: &LogEntry) -> String {
+    format!("{:>7.1}s  {}", entry.time.as_secs_f32(), entry.message)
+}
+
+fn modified_time(path: &str) -> Option<SystemTime> {
+    fs::metadata(path).and_then(|metadata| metadata.modified()).ok()
 }
 
 fn project_exists(project_name: &str) -> bool {
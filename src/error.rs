@@ -0,0 +1,69 @@
+use std::path::PathBuf;
+use thiserror::Error;
+
+#[derive(Debug, Error)]
+pub enum CreateError {
+    #[error("Project name cannot be empty.")]
+    EmptyName,
+    #[error("'{0}' can't be used as a folder name, leave out / \\ : * ? \" < > | and spaces at either end.")]
+    InvalidProjectName(String),
+    #[error("'{0}' can't be used as a crate name, {1}.")]
+    InvalidCrateName(String, &'static str),
+    #[error("'{0}' is not a valid class prefix, use letters, digits and '_' and start with a letter.")]
+    InvalidClassPrefix(String),
+    #[error("Project with this name already exists.")]
+    AlreadyExists,
+    #[error("Templates are not available.")]
+    TemplatesUnavailable,
+    #[error("Template '{0}' is missing from templates.yaml.")]
+    MissingTemplate(&'static str),
+    #[error("The gdextension template is not a valid .gdextension: {0}")]
+    InvalidGdExtensionTemplate(String),
+    #[error("{} is not a valid .gdextension: {message}", path.display())]
+    InvalidGdExtension { path: PathBuf, message: String },
+    #[error("'{0}' is not a valid class name.")]
+    InvalidClassName(String),
+    #[error("Class '{0}' is defined more than once.")]
+    DuplicateClass(String),
+    #[error("Failed to create directory {}: {source}", path.display())]
+    CreateDir { path: PathBuf, source: std::io::Error },
+    #[error("Failed to write {}: {source}", path.display())]
+    WriteFile { path: PathBuf, source: std::io::Error },
+    #[error("Invalid project metadata in {}: {source}", path.display())]
+    InvalidMetadata { path: PathBuf, source: serde_yaml::Error },
+    #[error("Failed to start cargo build process: {0}")]
+    SpawnCargo(std::io::Error),
+    #[error("Failed to start Godot: {0}")]
+    SpawnGodot(std::io::Error),
+    #[error("Failed to start the code editor: {0}")]
+    SpawnEditor(std::io::Error),
+    #[error("Failed to start the file manager: {0}")]
+    SpawnFileManager(std::io::Error),
+    #[error("Failed to start git: {0}")]
+    SpawnGit(std::io::Error),
+    #[error("git {0} failed.")]
+    GitFailed(&'static str),
+    #[error("Failed to start rustup: {0}")]
+    SpawnRustup(std::io::Error),
+    #[error("rustup target add failed.")]
+    RustupFailed,
+    #[error("Failed to compile Rust library.")]
+    BuildFailed,
+    #[error("Rust library file does not exist.")]
+    MissingLibrary,
+    #[error("{failed} of {iterations} reload iterations reported loader errors.")]
+    ReloadFailed { failed: u32, iterations: u32 },
+    #[error("None of the selected targets builds for this machine, the editor has no library to reload.")]
+    NoHostTarget,
+    #[error("{} loads '{loaded}' for {target}, but the stress test rebuilds '{rebuilt}'.", path.display())]
+    ReloadLibraryMismatch {
+        path: PathBuf,
+        target: String,
+        loaded: String,
+        rebuilt: String,
+    },
+    #[error("The headless editor exited or stopped answering during the reload stress test.")]
+    EditorUnresponsive,
+    #[error("Creation cancelled.")]
+    Cancelled,
+}
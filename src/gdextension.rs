@@ -0,0 +1,239 @@
+use std::fmt;
+
+// A .gdextension file as its sections in order, comments and entries nothing edits are written back as they were
+#[derive(Clone, Default)]
+pub struct GdExtensionFile {
+    // Lines before the first [section] are kept in one named ""
+    sections: Vec<Section>,
+}
+
+#[derive(Clone)]
+struct Section {
+    name: String,
+    lines: Vec<Line>,
+}
+
+#[derive(Clone)]
+enum Line {
+    // The value as written, e.g. "res://bin/libdemo.so" with its quotes or 4.2
+    Entry { key: String, value: String },
+    // Blank lines and ; or # comments
+    Other(String),
+}
+
+impl GdExtensionFile {
+    pub fn parse(content: &str) -> Result<Self, String> {
+        let mut sections = vec![Section {
+            name: String::new(),
+            lines: Vec::new(),
+        }];
+        for (index, line) in content.lines().enumerate() {
+            let trimmed = line.trim();
+            let lines = &mut sections.last_mut().expect("there is always a section").lines;
+            // Dictionaries and arrays, e.g. in [dependencies], may continue over several lines
+            if let Some(Line::Entry { value, .. }) = lines.last_mut().filter(|line| line.is_open()) {
+                value.push('\n');
+                value.push_str(line);
+            } else if let Some(name) = trimmed.strip_prefix('[').and_then(|rest| rest.strip_suffix(']')) {
+                sections.push(Section {
+                    name: name.trim().to_string(),
+                    lines: Vec::new(),
+                });
+            } else if trimmed.is_empty() || trimmed.starts_with(';') || trimmed.starts_with('#') {
+                lines.push(Line::Other(line.to_string()));
+            } else if let Some((key, value)) = trimmed.split_once('=') {
+                lines.push(Line::Entry {
+                    key: key.trim().to_string(),
+                    value: value.trim().to_string(),
+                });
+            } else {
+                return Err(format!("Line {}: expected [section] or key = value, found '{}'.", index + 1, trimmed));
+            }
+        }
+        Ok(Self { sections })
+    }
+
+    pub fn get(&self, section: &str, key: &str) -> Option<&str> {
+        self.entries(section).into_iter().find(|(entry, _)| *entry == key).map(|(_, value)| value)
+    }
+
+    pub fn entries(&self, section: &str) -> Vec<(&str, &str)> {
+        self.sections
+            .iter()
+            .filter(|candidate| candidate.name == section)
+            .flat_map(|section| &section.lines)
+            .filter_map(|line| match line {
+                Line::Entry { key, value } => Some((key.as_str(), value.as_str())),
+                Line::Other(_) => None,
+            })
+            .collect()
+    }
+
+    // Replaces the value in place, a new key goes after the section's last entry and a new section at the end
+    pub fn set(&mut self, section: &str, key: &str, value: String) {
+        if let Some(Line::Entry { value: existing, .. }) = self
+            .sections
+            .iter_mut()
+            .filter(|candidate| candidate.name == section)
+            .flat_map(|section| &mut section.lines)
+            .find(|line| matches!(line, Line::Entry { key: existing, .. } if existing == key))
+        {
+            *existing = value;
+            return;
+        }
+
+        let index = match self.sections.iter().position(|candidate| candidate.name == section) {
+            Some(index) => index,
+            None => {
+                if let Some(last) = self.sections.last_mut().filter(|last| !last.name.is_empty() || !last.lines.is_empty()) {
+                    if !matches!(last.lines.last(), Some(Line::Other(line)) if line.trim().is_empty()) {
+                        last.lines.push(Line::Other(String::new()));
+                    }
+                }
+                self.sections.push(Section {
+                    name: section.to_string(),
+                    lines: Vec::new(),
+                });
+                self.sections.len() - 1
+            }
+        };
+        let lines = &mut self.sections[index].lines;
+        let position = lines.iter().rposition(|line| matches!(line, Line::Entry { .. })).map_or(0, |last| last + 1);
+        lines.insert(position, Line::Entry { key: key.to_string(), value });
+    }
+
+    pub fn remove(&mut self, section: &str, key: &str) {
+        for section in self.sections.iter_mut().filter(|candidate| candidate.name == section) {
+            section
+                .lines
+                .retain(|line| !matches!(line, Line::Entry { key: existing, .. } if existing == key));
+        }
+    }
+}
+
+impl Line {
+    fn is_open(&self) -> bool {
+        let Line::Entry { value, .. } = self else {
+            return false;
+        };
+        let mut depth = 0;
+        let mut in_string = false;
+        let mut escaped = false;
+        for c in value.chars() {
+            match c {
+                _ if escaped => escaped = false,
+                '\\' if in_string => escaped = true,
+                '"' => in_string = !in_string,
+                '{' | '[' if !in_string => depth += 1,
+                '}' | ']' if !in_string => depth -= 1,
+                _ => {}
+            }
+        }
+        depth > 0
+    }
+}
+
+impl fmt::Display for GdExtensionFile {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for section in &self.sections {
+            if !section.name.is_empty() {
+                writeln!(f, "[{}]", section.name)?;
+            }
+            for line in &section.lines {
+                match line {
+                    Line::Entry { key, value } => writeln!(f, "{} = {}", key, value)?,
+                    Line::Other(line) => writeln!(f, "{}", line)?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+// "\"res://bin/libdemo.so\"" -> "res://bin/libdemo.so", anything but a string is returned as written
+pub fn unquote(value: &str) -> String {
+    match value.strip_prefix('"').and_then(|rest| rest.strip_suffix('"')) {
+        Some(inner) => inner.replace("\\\"", "\"").replace("\\\\", "\\"),
+        None => value.to_string(),
+    }
+}
+
+pub fn quote(value: &str) -> String {
+    format!("\"{}\"", value.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const GDEXTENSION: &str = "; Written by gen_gdext\n\
+[configuration]\n\
+entry_symbol = \"gdext_rust_init\"\n\
+# kept as written\n\
+compatibility_minimum = 4.2\n\
+\n\
+[libraries]\n\
+linux.debug.x86_64 = \"res://bin/a=b/lib]demo.so\"\n\
+linux.debug.x86_64 = \"res://rust/target/debug/libdemo.so\"\n\
+\n\
+[dependencies]\n\
+linux.debug.x86_64 = {\n\
+    \"res://bin/libdep.so\": \"\"\n\
+}\n";
+
+    #[test]
+    fn round_trip_keeps_comments_and_values() {
+        let file = GdExtensionFile::parse(GDEXTENSION).unwrap();
+        assert_eq!(file.to_string(), GDEXTENSION);
+        assert_eq!(file.get("configuration", "entry_symbol"), Some("\"gdext_rust_init\""));
+        assert_eq!(file.get("configuration", "compatibility_minimum"), Some("4.2"));
+        assert_eq!(file.entries("dependencies").len(), 1);
+    }
+
+    #[test]
+    fn quoted_values_keep_equals_and_brackets() {
+        let file = GdExtensionFile::parse(GDEXTENSION).unwrap();
+        let value = file.get("libraries", "linux.debug.x86_64").unwrap();
+        assert_eq!(unquote(value), "res://bin/a=b/lib]demo.so");
+        assert_eq!(unquote(&quote("res://a\"b\\c")), "res://a\"b\\c");
+    }
+
+    #[test]
+    fn repeated_key_is_kept_and_set_changes_the_first() {
+        let mut file = GdExtensionFile::parse(GDEXTENSION).unwrap();
+        assert_eq!(file.entries("libraries").len(), 2);
+        file.set("libraries", "linux.debug.x86_64", quote("res://bin/libdemo.so"));
+        assert_eq!(
+            file.entries("libraries"),
+            vec![
+                ("linux.debug.x86_64", "\"res://bin/libdemo.so\""),
+                ("linux.debug.x86_64", "\"res://rust/target/debug/libdemo.so\""),
+            ]
+        );
+    }
+
+    #[test]
+    fn remove_then_display() {
+        let mut file = GdExtensionFile::parse(GDEXTENSION).unwrap();
+        file.remove("libraries", "linux.debug.x86_64");
+        file.remove("configuration", "compatibility_minimum");
+        file.set("icons", "DemoPlayer", quote("res://icons/demo_player.svg"));
+        assert_eq!(
+            file.to_string(),
+            "; Written by gen_gdext\n\
+[configuration]\n\
+entry_symbol = \"gdext_rust_init\"\n\
+# kept as written\n\
+\n\
+[libraries]\n\
+\n\
+[dependencies]\n\
+linux.debug.x86_64 = {\n\
+    \"res://bin/libdep.so\": \"\"\n\
+}\n\
+\n\
+[icons]\n\
+DemoPlayer = \"res://icons/demo_player.svg\"\n"
+        );
+    }
+}
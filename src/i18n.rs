@@ -0,0 +1,387 @@
+use crate::utils::detect_system_locale;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::atomic::{AtomicU8, Ordering};
+use std::sync::OnceLock;
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Language {
+    // Follows the desktop's locale, English unless a translation for it exists
+    #[default]
+    System,
+    English,
+    German,
+}
+
+impl Language {
+    pub const ALL: [Language; 3] = [Language::System, Language::English, Language::German];
+
+    // Each language in itself, so it can be found without reading the current one
+    pub fn label(self) -> &'static str {
+        match self {
+            Language::System => tr("System"),
+            Language::English => "English",
+            Language::German => "Deutsch",
+        }
+    }
+
+    fn resolve(self) -> Language {
+        match self {
+            Language::System => match detect_system_locale() {
+                Some(locale) if locale.starts_with("de") => Language::German,
+                _ => Language::English,
+            },
+            language => language,
+        }
+    }
+}
+
+// The English texts are the keys, so one without a translation is shown as written
+static LANGUAGE: AtomicU8 = AtomicU8::new(Language::English as u8);
+
+pub fn set_language(language: Language) {
+    LANGUAGE.store(language.resolve() as u8, Ordering::Relaxed);
+}
+
+pub fn tr(text: &'static str) -> &'static str {
+    static GERMAN_TABLE: OnceLock<HashMap<&str, &str>> = OnceLock::new();
+    if LANGUAGE.load(Ordering::Relaxed) != Language::German as u8 {
+        return text;
+    }
+    GERMAN_TABLE.get_or_init(|| GERMAN.into_iter().collect()).get(text).copied().unwrap_or(text)
+}
+
+// Fills the translation's {} in order, translations keep the placeholders of the English text
+pub fn tr_fmt(text: &'static str, args: &[&dyn fmt::Display]) -> String {
+    let mut output = String::new();
+    let mut args = args.iter();
+    for (index, part) in tr(text).split("{}").enumerate() {
+        if index > 0 {
+            if let Some(arg) = args.next() {
+                output.push_str(&arg.to_string());
+            }
+        }
+        output.push_str(part);
+    }
+    output
+}
+
+const GERMAN: [(&str, &str); 216] = [
+    ("GDExtension Project Creator", "GDExtension-Projektgenerator"),
+    ("Logs will appear here...", "Hier erscheint das Protokoll..."),
+    ("Language:", "Sprache:"),
+    ("Check for updates on startup", "Beim Start nach Updates suchen"),
+    ("gen_gdext {} is available, this is {}.", "gen_gdext {} ist verfügbar, dies ist {}."),
+    ("Release page", "Release-Seite"),
+    ("What's new", "Neuerungen"),
+    ("Failed to read {}: {}", "{} konnte nicht gelesen werden: {}"),
+    ("{} is not a valid .gdextension. {}", "{} ist keine gültige .gdextension. {}"),
+    ("The entry symbol can't be empty.", "Das Entry-Symbol darf nicht leer sein."),
+    ("Min Godot version: {}", "Min. Godot-Version: {}"),
+    ("Max Godot version: {}", "Max. Godot-Version: {}"),
+    ("No .gdextension is open.", "Keine .gdextension geöffnet."),
+    ("Failed to write {}: {}", "{} konnte nicht geschrieben werden: {}"),
+    ("Create Project", "Projekt erstellen"),
+    ("Preview", "Vorschau"),
+    ("Templates", "Vorlagen"),
+    ("Cancel", "Abbrechen"),
+    ("Add \"Rebuild Rust\" editor plugin", "Editor-Plugin \"Rebuild Rust\" hinzufügen"),
+    (
+        "Double precision (needs a Godot build with precision=double)",
+        "Doppelte Genauigkeit (braucht einen Godot-Build mit precision=double)",
+    ),
+    (
+        "Shared prelude module with node and signal helper macros",
+        "Gemeinsames Prelude-Modul mit Hilfsmakros für Nodes und Signale",
+    ),
+    (
+        "Code style: rustfmt.toml, clippy.toml and lints in Cargo.toml",
+        "Codestil: rustfmt.toml, clippy.toml und Lints in Cargo.toml",
+    ),
+    (
+        "Integration tests run in a headless Godot (run_itest.sh)",
+        "Integrationstests in einem Godot ohne Fenster (run_itest.sh)",
+    ),
+    (
+        "Editor icons for the Rust classes in the Create Node dialog",
+        "Editor-Icons für die Rust-Klassen im Dialog \"Node erstellen\"",
+    ),
+    ("README.md describing the layout and how to build", "README.md mit Aufbau und Build-Anleitung"),
+    ("GitHub Actions workflow building every target", "GitHub-Actions-Workflow, der jedes Ziel baut"),
+    (
+        "Godot export presets for the selected platforms",
+        "Godot-Exportvorlagen für die gewählten Plattformen",
+    ),
+    (
+        "VS Code settings, build tasks and CodeLLDB debug configurations",
+        "VS-Code-Einstellungen, Build-Tasks und CodeLLDB-Debugkonfigurationen",
+    ),
+    ("Build recipes:", "Build-Rezepte:"),
+    ("None", "Keine"),
+    ("Library paths:", "Bibliothekspfade:"),
+    ("cargo's target dir", "Zielordner von cargo"),
+    (
+        "bin/<platform>/, filled by copy_libs scripts and after builds",
+        "bin/<Plattform>/, gefüllt von copy_libs-Skripten und nach Builds",
+    ),
+    ("target dir in the editor, bin/ in exports", "Zielordner im Editor, bin/ in Exporten"),
+    ("Cargo target dir:", "Cargo-Zielordner:"),
+    ("target (relative to the crate)", "target (relativ zum Crate)"),
+    (
+        "Minimal size release profile (opt-level \"z\", LTO, panic = \"abort\", strip)",
+        "Release-Profil für minimale Größe (opt-level \"z\", LTO, panic = \"abort\", strip)",
+    ),
+    (
+        "Precompile Rust Library and GdExtension (this takes a while)",
+        "Rust-Bibliothek und GdExtension vorkompilieren (dauert eine Weile)",
+    ),
+    ("Other platforms build with:", "Andere Plattformen bauen mit:"),
+    ("Open in Godot when done", "Danach in Godot öffnen"),
+    (
+        "Open the Rust crate in the code editor when done",
+        "Danach das Rust-Crate im Code-Editor öffnen",
+    ),
+    ("Template fetching stopped unexpectedly: {}", "Abrufen der Vorlagen unerwartet abgebrochen: {}"),
+    ("Template loading stopped unexpectedly.", "Laden der Vorlagen unerwartet abgebrochen."),
+    ("Prerequisites: checking...", "Voraussetzungen: werden geprüft..."),
+    ("Prerequisites: {} failed", "Voraussetzungen: {} nicht erfüllt"),
+    ("Prerequisites: all passed", "Voraussetzungen: alle erfüllt"),
+    ("Check again", "Erneut prüfen"),
+    ("Theme:", "Design:"),
+    ("System", "System"),
+    ("Dark", "Dunkel"),
+    ("Light", "Hell"),
+    ("Loading templates...", "Vorlagen werden geladen..."),
+    (", written for gdext {}", ", geschrieben für gdext {}"),
+    ("Templates loaded from {}{}", "Vorlagen geladen aus {}{}"),
+    ("Refresh templates", "Vorlagen aktualisieren"),
+    ("Reload templates", "Vorlagen neu laden"),
+    ("Template source:", "Vorlagenquelle:"),
+    ("templates.yaml, a git or an https URL", "templates.yaml, eine Git- oder https-URL"),
+    ("Project created successfully.", "Projekt erfolgreich erstellt."),
+    ("Creation failed", "Erstellen fehlgeschlagen"),
+    (
+        "'{}' differs from the generated version. Overwrite it?",
+        "'{}' weicht von der generierten Version ab. Überschreiben?",
+    ),
+    ("File conflict", "Dateikonflikt"),
+    ("Overwrite", "Überschreiben"),
+    ("Keep existing", "Bestehende behalten"),
+    ("{}\nCreate the project anyway?", "{}\nDas Projekt trotzdem erstellen?"),
+    ("Name collision", "Namenskonflikt"),
+    ("Create anyway", "Trotzdem erstellen"),
+    ("Class added successfully.", "Klasse erfolgreich hinzugefügt."),
+    ("Adding class failed", "Hinzufügen der Klasse fehlgeschlagen"),
+    ("Reload stress test passed.", "Reload-Stresstest bestanden."),
+    ("Reload stress test failed", "Reload-Stresstest fehlgeschlagen"),
+    ("{}: the task stopped unexpectedly.", "{}: die Aufgabe wurde unerwartet beendet."),
+    ("The task stopped unexpectedly.", "Die Aufgabe wurde unerwartet beendet."),
+    ("Library built.", "Bibliothek gebaut."),
+    ("Build failed", "Build fehlgeschlagen"),
+    ("Next steps for {}", "Nächste Schritte für {}"),
+    ("Dismiss", "Ausblenden"),
+    ("Copy", "Kopieren"),
+    ("Build now", "Jetzt bauen"),
+    ("Open in Godot", "In Godot öffnen"),
+    ("Open in editor", "Im Editor öffnen"),
+    ("Opening Godot failed: {}", "Godot konnte nicht geöffnet werden: {}"),
+    ("Opening the code editor failed: {}", "Der Code-Editor konnte nicht geöffnet werden: {}"),
+    ("{}/{} crates", "{}/{} Crates"),
+    ("{} crates", "{} Crates"),
+    ("Project Name:", "Projektname:"),
+    ("Godot Version:", "Godot-Version:"),
+    ("Display Name:", "Anzeigename:"),
+    ("Same as project name", "Wie der Projektname"),
+    ("Crate Name:", "Crate-Name:"),
+    ("Class Prefix:", "Klassenpräfix:"),
+    ("Project Type:", "Projekttyp:"),
+    ("Game", "Spiel"),
+    (
+        "Editor addon (EditorPlugin class under addons/)",
+        "Editor-Addon (EditorPlugin-Klasse unter addons/)",
+    ),
+    ("Renderer:", "Renderer:"),
+    ("Fallback locale:", "Ersatz-Locale:"),
+    ("License:", "Lizenz:"),
+    ("Author:", "Autor:"),
+    ("Rust Crate:", "Rust-Crate:"),
+    ("rust/ inside the project", "rust/ im Projekt"),
+    (
+        "<name>_rust/ next to the project (loaded via res://../)",
+        "<name>_rust/ neben dem Projekt (geladen über res://../)",
+    ),
+    ("<name>/godot/ and <name>/rust/ side by side", "<name>/godot/ und <name>/rust/ nebeneinander"),
+    ("Base Class:", "Basisklasse:"),
+    ("Reloadable", "Neu ladbar"),
+    ("Entry Symbol:", "Entry-Symbol:"),
+    ("Max Godot Version:", "Max. Godot-Version:"),
+    ("No maximum", "Kein Maximum"),
+    ("Extension Crates:", "Erweiterungs-Crates:"),
+    ("e.g. editor_tools, networking", "z. B. editor_tools, networking"),
+    ("Targets:", "Ziele:"),
+    ("host {}", "Host {}"),
+    ("detecting host...", "Host wird erkannt..."),
+    ("host unknown, rustc was not found", "Host unbekannt, rustc wurde nicht gefunden"),
+    ("godot crate features:", "Features des godot-Crates:"),
+    (
+        "api-custom (needs GODOT4_BIN set to the Godot build at compile time)",
+        "api-custom (braucht beim Kompilieren GODOT4_BIN mit dem Godot-Build)",
+    ),
+    (
+        "Dependencies (newest version from crates.io when creating):",
+        "Abhängigkeiten (neueste Version von crates.io beim Erstellen):",
+    ),
+    ("Post-create commands", "Befehle nach dem Erstellen"),
+    (
+        "Run in the new project's folder after creation, one shell command per line:",
+        "Laufen nach dem Erstellen im Ordner des neuen Projekts, ein Shell-Befehl pro Zeile:",
+    ),
+    ("Also run the template set's commands first:", "Vorher auch die Befehle der Vorlagen ausführen:"),
+    ("Initialize git repository", "Git-Repository anlegen"),
+    ("Commit message:", "Commit-Nachricht:"),
+    ("If the project exists:", "Wenn das Projekt existiert:"),
+    ("Refuse", "Ablehnen"),
+    ("Update changed files", "Geänderte Dateien aktualisieren"),
+    ("Add missing files", "Fehlende Dateien ergänzen"),
+    ("Add Rust to a Godot project", "Rust zu einem Godot-Projekt hinzufügen"),
+    (
+        "Adds the crate, .gdextension and .gitignore entries, project.godot and scenes are left alone",
+        "Fügt das Crate, die .gdextension und .gitignore-Einträge hinzu, project.godot und Szenen bleiben unverändert",
+    ),
+    ("Godot projects here:", "Godot-Projekte hier:"),
+    (
+        "none, type the project's folder as the project name",
+        "keine, den Ordner des Projekts als Projektnamen eingeben",
+    ),
+    ("Minimal class", "Minimale Klasse"),
+    (
+        "Example with a property, signal, functions and tests",
+        "Beispiel mit Property, Signal, Funktionen und Tests",
+    ),
+    ("Additional classes:", "Weitere Klassen:"),
+    ("Layout:", "Aufbau:"),
+    ("All in lib.rs", "Alles in lib.rs"),
+    ("classes/ module per class", "classes/ mit einem Modul pro Klasse"),
+    ("gameplay/, systems/, ui/ folders", "Ordner gameplay/, systems/, ui/"),
+    ("Remove", "Entfernen"),
+    ("Add class", "Klasse hinzufügen"),
+    ("Add from list", "Aus Liste hinzufügen"),
+    ("Release profile:", "Release-Profil:"),
+    ("Sign libraries with minisign, key:", "Bibliotheken mit minisign signieren, Schlüssel:"),
+    (
+        "Template variables, next to the built-in {}:",
+        "Vorlagenvariablen, zusätzlich zu den eingebauten {}:",
+    ),
+    ("Add template variable", "Vorlagenvariable hinzufügen"),
+    ("Cargo executable:", "Cargo-Programm:"),
+    ("Extra cargo build flags:", "Zusätzliche Flags für cargo build:"),
+    ("Godot executable:", "Godot-Programm:"),
+    ("Detect", "Erkennen"),
+    ("Code editor command:", "Befehl für den Code-Editor:"),
+    ("Environment variables for cargo and godot:", "Umgebungsvariablen für cargo und godot:"),
+    ("Add variable", "Variable hinzufügen"),
+    ("Load from project", "Aus Projekt laden"),
+    ("Save to project", "Im Projekt speichern"),
+    ("Add class to existing project:", "Klasse zu bestehendem Projekt hinzufügen:"),
+    ("Editor icon (optional):", "Editor-Icon (optional):"),
+    ("Reload stress test, iterations:", "Reload-Stresstest, Durchläufe:"),
+    ("Run", "Starten"),
+    ("Overwrite project", "Projekt überschreiben"),
+    (
+        "Every generated file in '{}' will be replaced, including ones you edited. Continue?",
+        "Jede generierte Datei in '{}' wird ersetzt, auch von dir bearbeitete. Fortfahren?",
+    ),
+    ("Preset:", "Voreinstellung:"),
+    ("Preset name", "Name der Voreinstellung"),
+    ("Save", "Speichern"),
+    ("Delete", "Löschen"),
+    ("Recent projects ({})", "Letzte Projekte ({})"),
+    ("Open folder", "Ordner öffnen"),
+    ("Re-create with same settings", "Mit gleichen Einstellungen neu erstellen"),
+    ("Forget", "Vergessen"),
+    ("(folder is gone)", "(Ordner existiert nicht mehr)"),
+    ("Opening the folder failed: {}", "Der Ordner konnte nicht geöffnet werden: {}"),
+    ("Quit", "Beenden"),
+    ("Stopping the running task...", "Die laufende Aufgabe wird gestoppt..."),
+    (
+        "A task is still running. Quitting stops it, kills the processes it started and rolls back what it created.",
+        "Eine Aufgabe läuft noch. Beenden stoppt sie, beendet die gestarteten Prozesse und macht Erstelltes rückgängig.",
+    ),
+    ("Stop and quit", "Stoppen und beenden"),
+    ("Keep running", "Weiterlaufen lassen"),
+    ("<project name>", "<Projektname>"),
+    ("Template Editor", "Vorlageneditor"),
+    ("The templates aren't loaded.", "Die Vorlagen sind nicht geladen."),
+    ("Save to {}", "In {} speichern"),
+    (
+        "Remote templates can't be saved, copy them to a local file and use that as the template source.",
+        "Entfernte Vorlagen können nicht gespeichert werden, kopiere sie in eine lokale Datei und nutze diese als Vorlagenquelle.",
+    ),
+    ("Revert", "Verwerfen"),
+    (
+        "Unsaved changes, the preview already uses them.",
+        "Ungespeicherte Änderungen, die Vorschau nutzt sie bereits.",
+    ),
+    (".gdextension Editor", ".gdextension-Editor"),
+    ("File:", "Datei:"),
+    ("Open", "Öffnen"),
+    ("Editing {}", "Bearbeitet wird {}"),
+    ("Min Godot Version:", "Min. Godot-Version:"),
+    (
+        "Reloadable (hot reload when the library is rebuilt)",
+        "Neu ladbar (Hot Reload, wenn die Bibliothek neu gebaut wird)",
+    ),
+    ("Libraries:", "Bibliotheken:"),
+    ("Cargo profile:", "Cargo-Profil:"),
+    ("Log:", "Protokoll:"),
+    ("Copy log", "Protokoll kopieren"),
+    ("Save log to file…", "Protokoll in Datei speichern…"),
+    ("Save log", "Protokoll speichern"),
+    ("relative to the working directory", "relativ zum Arbeitsverzeichnis"),
+    (
+        "The selected targets need rustup targets that aren't installed:\n{}\nRun rustup target add for them now?",
+        "Die gewählten Ziele brauchen rustup-Targets, die nicht installiert sind:\n{}\nJetzt rustup target add dafür ausführen?",
+    ),
+    ("Missing rustup targets", "Fehlende rustup-Targets"),
+    ("Install", "Installieren"),
+    ("Build without them", "Ohne sie bauen"),
+    ("default", "Standard"),
+    ("native", "nativ"),
+    ("Built by the {} toolchain as is.", "Wird von der Toolchain {} direkt gebaut."),
+    ("⚠ cross", "⚠ Cross"),
+    (
+        "Cross build: needs the matching rustup target and a linker for that platform, or a build on the platform itself.",
+        "Cross-Build: braucht das passende rustup-Target und einen Linker für diese Plattform oder einen Build auf der Plattform selbst.",
+    ),
+    ("Build the library with `{} build` in {}", "Baue die Bibliothek mit `{} build` in {}"),
+    (", then run copy_libs to fill bin/", ", dann fülle bin/ mit copy_libs"),
+    (
+        "{} is a tool class, the editor runs it as soon as the extension loads",
+        "{} ist eine Tool-Klasse, der Editor führt sie aus, sobald die Erweiterung geladen ist",
+    ),
+    (
+        "Create a {} from the New Resource dialog of the FileSystem dock",
+        "Erstelle eine {} über den Dialog \"Neue Ressource\" im Dateisystem-Dock",
+    ),
+    ("Instantiate {} from GDScript with {}.new()", "Instanziiere {} in GDScript mit {}.new()"),
+    (
+        "{} is the root of main.tscn, add more from the Create Node dialog under {}",
+        "{} ist die Wurzel von main.tscn, weitere gibt es im Dialog \"Node erstellen\" unter {}",
+    ),
+    (", {} more classes register alongside it", ", {} weitere Klassen werden mit ihr registriert"),
+    (
+        "Rebuild while the editor is open, Godot hot reloads the library when it regains focus",
+        "Baue neu, während der Editor offen ist, Godot lädt die Bibliothek neu, sobald er wieder den Fokus hat",
+    ),
+    (
+        "Restart the editor after rebuilding, the extension isn't reloadable",
+        "Starte den Editor nach dem Neubauen neu, die Erweiterung ist nicht neu ladbar",
+    ),
+    ("Open the project in Godot {}", "Öffne das Projekt in Godot {}"),
+    ("Everything", "Alles"),
+    ("Steps, warnings and errors", "Schritte, Warnungen und Fehler"),
+    ("Warnings and errors", "Warnungen und Fehler"),
+    ("Errors only", "Nur Fehler"),
+];
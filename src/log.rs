@@ -0,0 +1,99 @@
+use std::time::{Duration, Instant};
+
+// Ordered by importance, the log shows the selected level and everything above it
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum LogLevel {
+    // What cargo, git and Godot print besides their warnings and errors
+    Output,
+    Info,
+    Warning,
+    Error,
+}
+
+impl LogLevel {
+    pub const ALL: [LogLevel; 4] = [LogLevel::Output, LogLevel::Info, LogLevel::Warning, LogLevel::Error];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LogLevel::Output => "output",
+            LogLevel::Info => "info",
+            LogLevel::Warning => "warning",
+            LogLevel::Error => "error",
+        }
+    }
+}
+
+pub struct LogEntry {
+    // Since the log was started or last cleared
+    pub time: Duration,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+pub struct Log {
+    started: Instant,
+    entries: Vec<LogEntry>,
+}
+
+impl Default for Log {
+    fn default() -> Self {
+        Self {
+            started: Instant::now(),
+            entries: Vec::new(),
+        }
+    }
+}
+
+impl Log {
+    // Every line of a multi-line message becomes an entry of its own
+    pub fn push(&mut self, level: LogLevel, message: &str) {
+        let time = self.started.elapsed();
+        self.entries.extend(message.lines().map(|line| LogEntry {
+            time,
+            level,
+            message: line.to_string(),
+        }));
+    }
+
+    pub fn info(&mut self, message: &str) {
+        self.push(LogLevel::Info, message);
+    }
+
+    pub fn warn(&mut self, message: &str) {
+        self.push(LogLevel::Warning, message);
+    }
+
+    pub fn error(&mut self, message: &str) {
+        self.push(LogLevel::Error, message);
+    }
+
+    pub fn entries(&self) -> &[LogEntry] {
+        &self.entries
+    }
+
+    // Every entry with its level, for bug reports the filter in the window doesn't apply
+    pub fn text(&self) -> String {
+        self.entries
+            .iter()
+            .map(|entry| format!("{:>7.1}s {:<7} {}\n", entry.time.as_secs_f32(), entry.level.name(), entry.message))
+            .collect()
+    }
+
+    pub fn clear(&mut self) {
+        self.started = Instant::now();
+        self.entries.clear();
+    }
+}
+
+// Sorts a line of tool output, cargo's diagnostics start with "warning" or "error" and run until the next blank line
+pub fn output_level(line: &str, current: LogLevel) -> LogLevel {
+    if line.starts_with("error") {
+        LogLevel::Error
+    } else if line.starts_with("warning") {
+        LogLevel::Warning
+    } else if line.trim().is_empty() {
+        LogLevel::Output
+    } else {
+        current
+    }
+}
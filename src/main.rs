@@ -1,22 +1,54 @@
 #![windows_subsystem = "windows"]
 
 mod app;
+mod error;
+mod gdextension;
+mod i18n;
+mod log;
+mod metadata;
+mod profile;
+mod rpc;
+mod settings;
+mod storage;
+mod template_source;
+mod update_check;
 mod utils;
 
 use eframe::egui;
 
 const WINDOW_TITLE: &str = "GDExtension Project Creator";
 const MIN_WINDOW_SIZE: (f32, f32) = (500.0, 460.0);
-const MAX_WINDOW_SIZE: (f32, f32) = (500.0, 460.0);
-const RESIZABLE: bool = false;
-const MAXIMIZE_BUTTON: bool = false;
+const DEFAULT_WINDOW_SIZE: (f32, f32) = (640.0, 720.0);
+const RESIZABLE: bool = true;
+const MAXIMIZE_BUTTON: bool = true;
+const RPC_FLAG: &str = "--rpc";
+// Hidden, for finding bottlenecks when generating many projects, optionally followed by the number of runs
+const PROFILE_FLAG: &str = "--profile-gen";
 
 #[tokio::main]
 async fn main() {
+    if std::env::args().any(|arg| arg == RPC_FLAG) {
+        rpc::serve().await;
+        return;
+    }
+    let args: Vec<String> = std::env::args().collect();
+    if let Some(position) = args.iter().position(|arg| arg == PROFILE_FLAG) {
+        let runs = args
+            .get(position + 1)
+            .and_then(|runs| runs.parse().ok())
+            .unwrap_or(profile::DEFAULT_PROFILE_RUNS);
+        profile::run(runs).await;
+        return;
+    }
+
+    let (settings, _) = settings::AppSettings::load();
+    i18n::set_language(settings.language);
+    let window_position = settings.window_position;
     let native_options = eframe::NativeOptions {
         viewport: egui::ViewportBuilder {
+            position: window_position.map(|[x, y]| egui::pos2(x, y)),
+            inner_size: Some(egui::vec2(DEFAULT_WINDOW_SIZE.0, DEFAULT_WINDOW_SIZE.1)),
             min_inner_size: Some(egui::vec2(MIN_WINDOW_SIZE.0, MIN_WINDOW_SIZE.1)),
-            max_inner_size: Some(egui::vec2(MAX_WINDOW_SIZE.0, MAX_WINDOW_SIZE.1)),
             resizable: Some(RESIZABLE),
             maximize_button: Some(MAXIMIZE_BUTTON),
             ..Default::default()
@@ -24,5 +56,13 @@ async fn main() {
         ..Default::default()
     };
 
-    let _ = eframe::run_native(WINDOW_TITLE, native_options, Box::new(|_| Ok(Box::new(app::App::default()))));
+    let _ = eframe::run_native(
+        i18n::tr(WINDOW_TITLE),
+        native_options,
+        Box::new(|creation_context| {
+            let app = app::App::default();
+            app.apply_theme(&creation_context.egui_ctx);
+            Ok(Box::new(app))
+        }),
+    );
 }
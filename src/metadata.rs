@@ -0,0 +1,125 @@
+use crate::error::CreateError;
+use crate::storage::{write_atomic, FileLock};
+use crate::utils::{ProjectOptions, CODE_EDITOR};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+
+pub const METADATA_FILE: &str = ".gen_gdext.yaml";
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum FilePolicy {
+    #[default]
+    Overwrite,
+    Never,
+    Prompt,
+    Merge,
+}
+
+#[derive(Clone, Default, Serialize, Deserialize)]
+pub struct ProjectMetadata {
+    // Keyed by path relative to the project directory, e.g. "rust/src/lib.rs"
+    #[serde(default)]
+    pub file_policies: BTreeMap<String, FilePolicy>,
+    // Passed to every cargo and godot process the tool runs for the project, e.g. GODOT4_BIN
+    #[serde(default)]
+    pub env: BTreeMap<String, String>,
+    // Empty means whichever cargo is on the PATH
+    #[serde(default)]
+    pub cargo: String,
+    // Appended to every cargo build, e.g. ["--features", "my_feature", "-Zbuild-std"]
+    #[serde(default)]
+    pub cargo_args: Vec<String>,
+    // Empty means whichever godot is on the PATH
+    #[serde(default)]
+    pub godot: String,
+    // The command the crate is opened with, its arguments go before the crate path, e.g. "code --new-window"
+    #[serde(default)]
+    pub editor: String,
+    // Relative to the crate, empty means the crate's own target/
+    #[serde(default)]
+    pub target_dir: String,
+    // Workspace members next to the main crate that build a library and .gdextension of their own
+    #[serde(default)]
+    pub extension_crates: Vec<String>,
+    // The names and template values the project was created with, filled into the files added to it later.
+    // Empty names are derived like they were at creation
+    #[serde(default)]
+    pub display_name: String,
+    #[serde(default)]
+    pub class_prefix: String,
+    #[serde(default)]
+    pub author: String,
+    #[serde(default)]
+    pub template_variables: BTreeMap<String, String>,
+}
+
+impl ProjectMetadata {
+    pub fn for_new_project(crate_dir: &str) -> Self {
+        let mut metadata = Self::default();
+        metadata.file_policies.insert(format!("{}/src/lib.rs", crate_dir), FilePolicy::Never);
+        metadata.file_policies.insert("main.tscn".to_string(), FilePolicy::Never);
+        metadata.file_policies.insert("icon.svg".to_string(), FilePolicy::Never);
+        // The editor rewrites it whenever an export preset is changed
+        metadata.file_policies.insert("export_presets.cfg".to_string(), FilePolicy::Never);
+        metadata.file_policies.insert(format!("{}/.gitignore", crate_dir), FilePolicy::Merge);
+        metadata
+    }
+
+    // The settings saved for the project as options, whose fallbacks builds and launches go through
+    pub fn saved_options(&self) -> ProjectOptions {
+        ProjectOptions {
+            env: self.env.clone(),
+            cargo: self.cargo.clone(),
+            cargo_args: self.cargo_args.clone(),
+            godot: self.godot.clone(),
+            target_dir: self.target_dir.clone(),
+            extension_crates: self.extension_crates.clone(),
+            display_name: self.display_name.clone(),
+            class_prefix: self.class_prefix.clone(),
+            author: self.author.clone(),
+            template_variables: self.template_variables.clone(),
+            ..ProjectOptions::default()
+        }
+    }
+
+    pub fn editor_command(&self) -> &str {
+        if self.editor.trim().is_empty() {
+            CODE_EDITOR
+        } else {
+            self.editor.trim()
+        }
+    }
+
+    pub fn load(project_dir: &Path) -> Result<Self, CreateError> {
+        let path = project_dir.join(METADATA_FILE);
+        match fs::read_to_string(&path) {
+            Ok(content) => serde_yaml::from_str(&content).map_err(|source| CreateError::InvalidMetadata { path, source }),
+            Err(_) => Ok(Self::default()),
+        }
+    }
+
+    // Load, change and save under the file lock, so a concurrent instance can't drop the other's changes
+    pub fn update(project_dir: &Path, change: impl FnOnce(&mut Self)) -> Result<(), CreateError> {
+        let path = project_dir.join(METADATA_FILE);
+        let _lock = FileLock::acquire(&path).map_err(|source| CreateError::WriteFile { path: path.clone(), source })?;
+        let mut metadata = Self::load(project_dir)?;
+        change(&mut metadata);
+        write_atomic(&path, &metadata.to_yaml()).map_err(|source| CreateError::WriteFile { path, source })
+    }
+
+    pub fn to_yaml(&self) -> String {
+        serde_yaml::to_string(self).expect("Failed to serialize project metadata")
+    }
+
+    pub fn policy(&self, relative_path: &str) -> FilePolicy {
+        match self.file_policies.get(relative_path) {
+            Some(policy) => *policy,
+            // Users edit this file to change policies, so it is only written once
+            None if relative_path == METADATA_FILE => FilePolicy::Never,
+            None => FilePolicy::default(),
+        }
+    }
+}
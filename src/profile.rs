@@ -0,0 +1,130 @@
+use crate::log::Log;
+use crate::utils::*;
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+pub const DEFAULT_PROFILE_RUNS: u32 = 20;
+const PROFILE_PROJECT: &str = "profile_project";
+
+// Each phase in the order creation runs it, with one sample per run
+struct Phase {
+    name: &'static str,
+    samples: Vec<Duration>,
+}
+
+// Generates the same project over and over in a scratch directory and prints where the time went
+pub async fn run(runs: u32) {
+    let scratch_dir = std::env::temp_dir().join(format!("gen_gdext_profile_{}", std::process::id()));
+    let template_path = std::env::current_dir().map(|dir| dir.join(TEMPLATE_FILE)).unwrap_or_default();
+    if let Err(err) = std::fs::create_dir_all(&scratch_dir).and_then(|()| std::env::set_current_dir(&scratch_dir)) {
+        eprintln!("Failed to prepare {}: {}", scratch_dir.display(), err);
+        return;
+    }
+
+    let options = profile_options();
+    let mut phases = ["template loading", "template rendering", "filesystem writes", "process spawns"].map(|name| Phase { name, samples: Vec::new() });
+    let log = Arc::new(Mutex::new(Log::default()));
+
+    for run in 1..=runs {
+        let start = Instant::now();
+        let templates = match read_templates(&template_path.to_string_lossy()).await {
+            Ok(templates) => templates,
+            Err(err) => {
+                eprintln!("{}", err);
+                break;
+            }
+        };
+        phases[0].samples.push(start.elapsed());
+
+        let start = Instant::now();
+        let files = match render_project_files(PROFILE_PROJECT, &templates, &options) {
+            Ok(files) => files,
+            Err(err) => {
+                eprintln!("Rendering failed: {}", err);
+                break;
+            }
+        };
+        phases[1].samples.push(start.elapsed());
+
+        let start = Instant::now();
+        let written = ProjectWriter::new(Path::new(PROFILE_PROJECT), &log, ExistingProject::Overwrite, &|_| true)
+            .and_then(|writer| writer.create_dir("").and_then(|()| write_project_files(&writer, &files)));
+        if let Err(err) = written {
+            eprintln!("Writing failed: {}", err);
+            break;
+        }
+        phases[2].samples.push(start.elapsed());
+
+        // The version queries stand in for the cargo, git and godot runs a creation starts
+        let start = Instant::now();
+        for program in [options.cargo_program(), "git", options.godot_program()] {
+            let _ = Command::new(program).arg("--version").stdout(Stdio::null()).stderr(Stdio::null()).status();
+        }
+        phases[3].samples.push(start.elapsed());
+
+        let _ = std::fs::remove_dir_all(PROFILE_PROJECT);
+        log.lock().unwrap().clear();
+        eprint!("\rRun {}/{}", run, runs);
+    }
+    eprintln!();
+
+    print_breakdown(&phases);
+    let _ = std::env::set_current_dir(std::env::temp_dir());
+    let _ = std::fs::remove_dir_all(&scratch_dir);
+}
+
+// Every optional file turned on so each template gets rendered and written
+fn profile_options() -> ProjectOptions {
+    ProjectOptions {
+        targets: KNOWN_TARGETS.iter().map(|target| target.to_string()).collect(),
+        existing_project: ExistingProject::Overwrite,
+        editor_plugin: true,
+        prelude_module: true,
+        code_style: true,
+        itest: true,
+        class_icons: true,
+        license: License::Mit,
+        library_layout: LibraryLayout::Split,
+        // A custom profile, so cargo_profile_custom is rendered too
+        target_profiles: KNOWN_TARGETS
+            .iter()
+            .filter(|target| target.contains(".release"))
+            .map(|target| (target.to_string(), "dist".to_string()))
+            .collect(),
+        readme: true,
+        github_workflow: true,
+        export_presets: true,
+        task_runner: TaskRunner::Just,
+        vscode: true,
+        ..Default::default()
+    }
+}
+
+fn print_breakdown(phases: &[Phase]) {
+    let total: Duration = phases.iter().flat_map(|phase| &phase.samples).sum();
+    println!(
+        "{:<20} {:>5} {:>10} {:>10} {:>10} {:>7}",
+        "phase", "runs", "min ms", "mean ms", "max ms", "share"
+    );
+    for phase in phases.iter().filter(|phase| !phase.samples.is_empty()) {
+        let sum: Duration = phase.samples.iter().sum();
+        let min = phase.samples.iter().min().copied().unwrap_or_default();
+        let max = phase.samples.iter().max().copied().unwrap_or_default();
+        println!(
+            "{:<20} {:>5} {:>10.3} {:>10.3} {:>10.3} {:>6.1}%",
+            phase.name,
+            phase.samples.len(),
+            min.as_secs_f64() * 1000.0,
+            sum.as_secs_f64() * 1000.0 / phase.samples.len() as f64,
+            max.as_secs_f64() * 1000.0,
+            if total.is_zero() {
+                0.0
+            } else {
+                sum.as_secs_f64() / total.as_secs_f64() * 100.0
+            }
+        );
+    }
+    println!("{:<20} {:>5} {:>10} {:>10.3}", "total", "", "", total.as_secs_f64() * 1000.0);
+}
@@ -0,0 +1,238 @@
+use crate::error::CreateError;
+use crate::log::Log;
+use crate::settings::AppSettings;
+use crate::template_source::TemplateSource;
+use crate::utils::*;
+use serde::{Deserialize, Deserializer};
+use serde_json::{json, Value};
+use std::io::Write;
+use std::sync::atomic::AtomicBool;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, BufReader};
+
+const PARSE_ERROR: i64 = -32700;
+const METHOD_NOT_FOUND: i64 = -32601;
+const INVALID_PARAMS: i64 = -32602;
+const CREATION_FAILED: i64 = -32000;
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+#[derive(Deserialize)]
+struct Request {
+    // Missing for notifications, an explicit null is still an id
+    #[serde(default, deserialize_with = "present")]
+    id: Option<Value>,
+    method: String,
+    #[serde(default)]
+    params: Value,
+}
+
+fn present<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<Value>, D::Error> {
+    Value::deserialize(deserializer).map(Some)
+}
+
+#[derive(Deserialize)]
+struct ProjectParams {
+    project_name: String,
+    #[serde(default)]
+    options: ProjectOptions,
+    #[serde(default)]
+    iterations: Option<u32>,
+    #[serde(default)]
+    class: ClassSpec,
+    #[serde(default)]
+    icon: String,
+}
+
+// Newline-delimited JSON-RPC 2.0 over stdio, the transport LSP/MCP style tooling expects
+pub async fn serve() {
+    let mut lines = BufReader::new(tokio::io::stdin()).lines();
+
+    while let Ok(Some(line)) = lines.next_line().await {
+        if line.trim().is_empty() {
+            continue;
+        }
+
+        let request = match serde_json::from_str::<Request>(&line) {
+            Ok(request) => request,
+            Err(err) => {
+                send(&error_response(Value::Null, PARSE_ERROR, &err.to_string()));
+                continue;
+            }
+        };
+
+        // A request without an id is a notification, it is handled but never answered, not even with an error
+        let id = request.id.clone();
+        let outcome = handle(request).await;
+        let Some(id) = id else {
+            continue;
+        };
+        let response = match outcome {
+            Ok(result) => json!({ "jsonrpc": "2.0", "id": id, "result": result }),
+            Err((code, message)) => error_response(id, code, &message),
+        };
+        send(&response);
+    }
+}
+
+async fn handle(request: Request) -> Result<Value, (i64, String)> {
+    match request.method.as_str() {
+        "templates/list" => {
+            let (path, templates, warning) = configured_templates().await.map_err(|err| (CREATION_FAILED, err))?;
+            let sections = templates.filled_sections();
+            let gitignore_fragments: Vec<&String> = templates.gitignore_fragments.keys().collect();
+            let export_presets: Vec<&String> = templates.export_presets.keys().collect();
+            let licenses: Vec<&String> = templates.licenses.keys().collect();
+            let requirements = &templates.requirements;
+            Ok(json!({
+                "path": path,
+                "source_warning": warning,
+                "requirements": json!({
+                    "min_tool_version": requirements.min_tool_version,
+                    "min_godot": requirements.min_godot.map(|version| version.to_string()),
+                    "max_godot": requirements.max_godot.map(|version| version.to_string()),
+                    "gdext": requirements.gdext,
+                }),
+                "sections": sections,
+                "gitignore_fragments": gitignore_fragments,
+                "post_create_hooks": templates.post_create_hooks,
+                "export_presets": export_presets,
+                "licenses": licenses,
+                "targets": KNOWN_TARGETS,
+                "godot_features": KNOWN_GODOT_FEATURES,
+                "dependencies": KNOWN_DEPENDENCIES.map(|(name, _, _)| name),
+            }))
+        }
+        "settings/validate" => {
+            let params = parse_params(request.params)?;
+            let templates = configured_templates().await.ok().map(|(_, templates, _)| templates);
+            let problems = validate_settings(&params.project_name, templates.as_ref(), &params.options);
+            let mut warnings = name_collisions(
+                &ProjectNames::new(&params.project_name, &params.options),
+                &params.options,
+                &AtomicBool::new(false),
+            );
+            if let Some(templates) = &templates {
+                warnings.extend(template_warnings(templates, &params.options.template_variables));
+            }
+            Ok(json!({ "valid": problems.is_empty(), "problems": problems, "warnings": warnings }))
+        }
+        "project/create" => {
+            let params = parse_params(request.params)?;
+            let (_, templates, _) = configured_templates().await.map_err(|err| (CREATION_FAILED, err))?;
+            let problems = validate_settings(&params.project_name, Some(&templates), &params.options);
+            if !problems.is_empty() {
+                return Err((INVALID_PARAMS, problems.join(" ")));
+            }
+
+            let project_name = params.project_name.clone();
+            let builds = Arc::new(Mutex::new(Vec::new()));
+            let task_builds = Arc::clone(&builds);
+            let skipped = Arc::new(Mutex::new(Vec::new()));
+            let task_skipped = Arc::clone(&skipped);
+            run_with_progress(move |log| {
+                // There is nobody to ask, so files with a prompt policy are kept as they are and listed in the result
+                let keep = |path: &str| {
+                    task_skipped.lock().unwrap().push(path.to_string());
+                    false
+                };
+                create_project(&params.project_name, log, &templates, &params.options, &AtomicBool::new(false), &keep, &task_builds)
+            })
+            .await?;
+            let skipped = skipped.lock().unwrap().clone();
+            Ok(json!({ "project_name": project_name, "targets": target_builds_json(&builds), "skipped": skipped }))
+        }
+        "project/build" => {
+            let params = parse_params(request.params)?;
+            let project_name = params.project_name.clone();
+            let builds = Arc::new(Mutex::new(Vec::new()));
+            let task_builds = Arc::clone(&builds);
+            run_with_progress(move |log| precompile_library(&params.project_name, &log, &params.options, &AtomicBool::new(false), &task_builds)).await?;
+            Ok(json!({ "project_name": project_name, "targets": target_builds_json(&builds) }))
+        }
+        "project/stress_reload" => {
+            let params = parse_params(request.params)?;
+            let project_name = params.project_name.clone();
+            let iterations = params.iterations.unwrap_or(DEFAULT_RELOAD_ITERATIONS);
+            run_with_progress(move |log| stress_test_reload(&params.project_name, &params.options, iterations, &log, &AtomicBool::new(false))).await?;
+            Ok(json!({ "project_name": project_name, "iterations": iterations }))
+        }
+        "project/add_class" => {
+            let params = parse_params(request.params)?;
+            let (_, templates, _) = configured_templates().await.map_err(|err| (CREATION_FAILED, err))?;
+            let project_name = params.project_name.clone();
+            let class_name = params.class.name.clone();
+            run_with_progress(move |log| add_class_to_project(&params.project_name, &templates, &params.class, &params.icon, &log)).await?;
+            Ok(json!({ "project_name": project_name, "class_name": class_name }))
+        }
+        _ => Err((METHOD_NOT_FOUND, format!("Unknown method '{}'.", request.method))),
+    }
+}
+
+// The templates the window uses, from the template source in the saved settings, remote ones from their cache
+async fn configured_templates() -> Result<(String, ProjectTemplates, Option<String>), String> {
+    let source = TemplateSource::parse(&AppSettings::load().0.template_source);
+    let (path, warning) = tokio::task::spawn_blocking(move || source.resolve(false))
+        .await
+        .map_err(|err| format!("Template fetching stopped unexpectedly: {}", err))??;
+    let path = path.to_string_lossy().into_owned();
+    let templates = read_templates(&path).await?;
+    Ok((path, templates, warning))
+}
+
+fn target_builds_json(builds: &Mutex<Vec<TargetBuild>>) -> Value {
+    Value::Array(
+        builds
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|build| json!({ "target": build.target, "built": build.status == TargetStatus::Built, "status": build.status.to_string() }))
+            .collect(),
+    )
+}
+
+fn parse_params(params: Value) -> Result<ProjectParams, (i64, String)> {
+    serde_json::from_value(params).map_err(|err| (INVALID_PARAMS, err.to_string()))
+}
+
+// Runs the blocking creation code off the runtime and forwards its log as `progress` notifications
+async fn run_with_progress<F>(task: F) -> Result<(), (i64, String)>
+where
+    F: FnOnce(Arc<Mutex<Log>>) -> Result<(), CreateError> + Send + 'static,
+{
+    let log = Arc::new(Mutex::new(Log::default()));
+    let task_log = Arc::clone(&log);
+    let handle = tokio::task::spawn_blocking(move || task(task_log));
+
+    let mut sent = 0;
+    while !handle.is_finished() {
+        tokio::time::sleep(PROGRESS_INTERVAL).await;
+        send_progress(&log, &mut sent);
+    }
+    let result = handle.await.map_err(|err| (CREATION_FAILED, err.to_string()))?;
+    send_progress(&log, &mut sent);
+
+    result.map_err(|err| (CREATION_FAILED, err.to_string()))
+}
+
+fn send_progress(log: &Arc<Mutex<Log>>, sent: &mut usize) {
+    let log = log.lock().unwrap();
+    for entry in &log.entries()[*sent..] {
+        send(&json!({
+            "jsonrpc": "2.0",
+            "method": "progress",
+            "params": json!({ "message": entry.message, "level": entry.level.name() }),
+        }));
+    }
+    *sent = log.entries().len();
+}
+
+fn error_response(id: Value, code: i64, message: &str) -> Value {
+    json!({ "jsonrpc": "2.0", "id": id, "error": json!({ "code": code, "message": message }) })
+}
+
+fn send(message: &Value) {
+    let mut stdout = std::io::stdout().lock();
+    let _ = writeln!(stdout, "{}", message);
+    let _ = stdout.flush();
+}
@@ -0,0 +1,193 @@
+use crate::i18n::Language;
+use crate::storage::{write_atomic, FileLock};
+use crate::utils::{
+    BaseClass, CrateLayout, CrateLocation, LibScaffold, LibraryLayout, ProjectFlavor, ReleaseProfile, TaskRunner, DEFAULT_GODOT_VERSION, KNOWN_TARGETS,
+};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+const SETTINGS_DIR: &str = "gen_gdext";
+const SETTINGS_FILE: &str = "settings.yaml";
+// Where a settings file that doesn't parse is moved, so the defaults written next don't replace it
+const SETTINGS_BACKUP_FILE: &str = "settings.yaml.bak";
+pub const RECENT_PROJECTS_LIMIT: usize = 10;
+
+// What the window remembers between runs, keys missing from an older file keep their defaults
+#[derive(Serialize, Deserialize)]
+#[serde(default)]
+pub struct AppSettings {
+    pub godot_version: String,
+    // The selected targets, the ones left out are shown unchecked
+    pub targets: Vec<String>,
+    pub reloadable: bool,
+    pub precompile_lib: bool,
+    // Top left corner of the window on the desktop, None lets the platform place it
+    pub window_position: Option<[f32; 2]>,
+    pub presets: BTreeMap<String, Preset>,
+    // A local path, git URL or https URL, empty is the templates.yaml in the working directory
+    pub template_source: String,
+    // Newest first, at most RECENT_PROJECTS_LIMIT
+    pub recent_projects: Vec<RecentProject>,
+    pub theme: Theme,
+    pub language: Language,
+    // Asks GitHub for a newer release on startup, off unless the user turns it on
+    pub check_for_updates: bool,
+    // The release whose banner was dismissed, it isn't shown again
+    pub dismissed_release: String,
+    // Run in every new project, one shell command each
+    pub post_create_hooks: Vec<String>,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Theme {
+    // Follows the desktop's dark or light mode
+    #[default]
+    System,
+    Dark,
+    Light,
+}
+
+// A project created from the window, kept with the settings it was created with
+#[derive(Clone, Serialize, Deserialize)]
+pub struct RecentProject {
+    pub name: String,
+    // Absolute, the window may be started from another directory next time
+    pub path: String,
+    pub preset: Preset,
+}
+
+// A named combination of creation options, e.g. "Mobile", the project name and per-project paths aren't part of it
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(default)]
+pub struct Preset {
+    pub template_source: String,
+    pub godot_version: String,
+    pub reloadable: bool,
+    pub targets: Vec<String>,
+    pub godot_features: Vec<String>,
+    pub dependencies: Vec<String>,
+    pub flavor: ProjectFlavor,
+    pub base_class: BaseClass,
+    pub layout: CrateLayout,
+    pub lib_scaffold: LibScaffold,
+    pub crate_location: CrateLocation,
+    pub library_layout: LibraryLayout,
+    pub task_runner: TaskRunner,
+    pub minimal_size: bool,
+    pub release_profile: ReleaseProfile,
+    pub target_profiles: BTreeMap<String, String>,
+    pub double_precision: bool,
+    pub prelude_module: bool,
+    pub code_style: bool,
+    pub itest: bool,
+    pub class_icons: bool,
+    pub editor_plugin: bool,
+    pub readme: bool,
+    pub github_workflow: bool,
+    pub export_presets: bool,
+    pub vscode: bool,
+}
+
+impl Default for AppSettings {
+    fn default() -> Self {
+        Self {
+            godot_version: DEFAULT_GODOT_VERSION.to_string(),
+            targets: KNOWN_TARGETS.iter().map(|target| target.to_string()).collect(),
+            reloadable: true,
+            precompile_lib: false,
+            window_position: None,
+            presets: BTreeMap::new(),
+            template_source: String::new(),
+            recent_projects: Vec::new(),
+            theme: Theme::System,
+            language: Language::System,
+            check_for_updates: false,
+            dismissed_release: String::new(),
+            post_create_hooks: Vec::new(),
+        }
+    }
+}
+
+impl AppSettings {
+    // A missing or unreadable file starts from the defaults, the settings are a convenience and never block the window.
+    // One that doesn't parse is moved aside first, the returned warning says where
+    pub fn load() -> (Self, Option<String>) {
+        let Some(path) = settings_path() else {
+            return (Self::default(), None);
+        };
+        let Ok(content) = fs::read_to_string(&path) else {
+            return (Self::default(), None);
+        };
+        match serde_yaml::from_str(&content) {
+            Ok(settings) => (settings, None),
+            Err(err) => {
+                let backup_path = path.with_file_name(SETTINGS_BACKUP_FILE);
+                let warning = match fs::rename(&path, &backup_path) {
+                    Ok(()) => format!(
+                        "{} doesn't parse ({}), it was moved to {} and the defaults are used.",
+                        path.display(),
+                        err,
+                        backup_path.display()
+                    ),
+                    Err(_) => format!("{} doesn't parse ({}), the defaults are used and nothing is saved.", path.display(), err),
+                };
+                (Self::default(), Some(warning))
+            }
+        }
+    }
+
+    // Load, change and save under the file lock like the project metadata, so two windows don't drop each other's presets
+    // and recent projects. Returns what was saved, a file that doesn't parse is left alone
+    pub fn update(change: impl FnOnce(&mut Self)) -> io::Result<Self> {
+        let path = settings_path().ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "no config directory"))?;
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        let _lock = FileLock::acquire(&path)?;
+        let mut settings = match fs::read_to_string(&path) {
+            Ok(content) => serde_yaml::from_str(&content).map_err(|err| {
+                io::Error::new(
+                    io::ErrorKind::InvalidData,
+                    format!("{} doesn't parse, not overwriting it: {}", path.display(), err),
+                )
+            })?,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => Self::default(),
+            Err(err) => return Err(err),
+        };
+        change(&mut settings);
+        let content = serde_yaml::to_string(&settings).map_err(|err| io::Error::new(io::ErrorKind::InvalidData, err.to_string()))?;
+        write_atomic(&path, &content)?;
+        Ok(settings)
+    }
+
+    // Creating into the same folder again moves it to the top instead of listing it twice
+    pub fn add_recent_project(&mut self, project: RecentProject) {
+        self.recent_projects.retain(|recent| recent.path != project.path);
+        self.recent_projects.insert(0, project);
+        self.recent_projects.truncate(RECENT_PROJECTS_LIMIT);
+    }
+}
+
+fn settings_path() -> Option<PathBuf> {
+    Some(config_dir()?.join(SETTINGS_FILE))
+}
+
+// The tool's folder under %APPDATA% on Windows, ~/Library/Application Support on macOS, $XDG_CONFIG_HOME or ~/.config elsewhere
+pub fn config_dir() -> Option<PathBuf> {
+    let home = std::env::var_os("HOME").map(PathBuf::from);
+    let config_dir = if cfg!(windows) {
+        std::env::var_os("APPDATA").map(PathBuf::from)
+    } else if cfg!(target_os = "macos") {
+        home.map(|home| home.join("Library/Application Support"))
+    } else {
+        std::env::var_os("XDG_CONFIG_HOME")
+            .map(PathBuf::from)
+            .filter(|dir| dir.is_absolute())
+            .or_else(|| home.map(|home| home.join(".config")))
+    };
+    Some(config_dir?.join(SETTINGS_DIR))
+}
@@ -0,0 +1,78 @@
+use std::fs;
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+use std::thread;
+use std::time::{Duration, Instant};
+
+const LOCK_RETRY_INTERVAL: Duration = Duration::from_millis(50);
+const LOCK_TIMEOUT: Duration = Duration::from_secs(5);
+// Nothing holds a lock this long, an older one was left behind by a crashed run
+const STALE_LOCK_AGE: Duration = Duration::from_secs(30);
+
+// Writes next to the file and renames over it, readers see the old or the new content but never half a file
+pub fn write_atomic(path: &Path, content: &str) -> io::Result<()> {
+    let temp_path = sibling_path(path, &format!(".{}.tmp", std::process::id()));
+    let written = fs::File::create(&temp_path)
+        .and_then(|mut file| {
+            file.write_all(content.as_bytes())?;
+            file.sync_all()
+        })
+        .and_then(|()| fs::rename(&temp_path, path));
+    if written.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    written
+}
+
+// Held as a `<file>.lock` next to the locked file so every instance and the RPC server see it, released on drop
+pub struct FileLock {
+    path: PathBuf,
+}
+
+impl FileLock {
+    pub fn acquire(path: &Path) -> io::Result<Self> {
+        let lock_path = sibling_path(path, ".lock");
+        let start = Instant::now();
+        loop {
+            match fs::OpenOptions::new().write(true).create_new(true).open(&lock_path) {
+                Ok(mut file) => {
+                    let _ = write!(file, "{}", std::process::id());
+                    return Ok(Self { path: lock_path });
+                }
+                Err(err) if err.kind() == io::ErrorKind::AlreadyExists => {
+                    if is_stale(&lock_path) {
+                        let _ = fs::remove_file(&lock_path);
+                    } else if start.elapsed() >= LOCK_TIMEOUT {
+                        return Err(io::Error::new(
+                            io::ErrorKind::TimedOut,
+                            format!("{} is held by another instance", lock_path.display()),
+                        ));
+                    } else {
+                        thread::sleep(LOCK_RETRY_INTERVAL);
+                    }
+                }
+                Err(err) => return Err(err),
+            }
+        }
+    }
+}
+
+impl Drop for FileLock {
+    fn drop(&mut self) {
+        let _ = fs::remove_file(&self.path);
+    }
+}
+
+fn is_stale(lock_path: &Path) -> bool {
+    fs::metadata(lock_path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+        .and_then(|modified| modified.elapsed().ok())
+        .is_some_and(|age| age > STALE_LOCK_AGE)
+}
+
+fn sibling_path(path: &Path, suffix: &str) -> PathBuf {
+    let mut name = path.file_name().unwrap_or_default().to_os_string();
+    name.push(suffix);
+    path.with_file_name(name)
+}
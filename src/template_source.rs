@@ -0,0 +1,121 @@
+use crate::settings::config_dir;
+use crate::utils::TEMPLATE_FILE;
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+
+const CACHE_DIR: &str = "template_cache";
+
+// Where the templates are read from, remote ones are fetched into a cache once and again on refresh
+#[derive(Clone, PartialEq, Eq)]
+pub enum TemplateSource {
+    File(String),
+    // A repository with templates.yaml at its root
+    Git(String),
+    // A URL serving a templates.yaml
+    Http(String),
+}
+
+impl TemplateSource {
+    // Empty is the local templates.yaml, git URLs are cloned and any other http(s) URL is downloaded
+    pub fn parse(source: &str) -> Self {
+        let source = source.trim();
+        if source.is_empty() {
+            TemplateSource::File(TEMPLATE_FILE.to_string())
+        } else if source.ends_with(".git") || ["git@", "git://", "ssh://"].iter().any(|scheme| source.starts_with(scheme)) {
+            TemplateSource::Git(source.to_string())
+        } else if source.starts_with("https://") || source.starts_with("http://") {
+            TemplateSource::Http(source.to_string())
+        } else {
+            TemplateSource::File(source.to_string())
+        }
+    }
+
+    pub fn is_remote(&self) -> bool {
+        !matches!(self, TemplateSource::File(_))
+    }
+
+    // The local templates.yaml of the source and a warning when a refresh failed and the cached copy stands in
+    pub fn resolve(&self, refresh: bool) -> Result<(PathBuf, Option<String>), String> {
+        let (url, cache) = match self {
+            TemplateSource::File(path) => return Ok((PathBuf::from(path), None)),
+            TemplateSource::Git(url) | TemplateSource::Http(url) => (url, cache_path(url).ok_or("There is no config directory to cache remote templates in.")?),
+        };
+        let cached = match self {
+            TemplateSource::Git(_) => cache.join(TEMPLATE_FILE),
+            _ => cache.clone(),
+        };
+        if cached.exists() && !refresh {
+            return Ok((cached, None));
+        }
+
+        let fetched = match self {
+            TemplateSource::Git(_) => fetch_git(url, &cache),
+            _ => fetch_http(url, &cache),
+        };
+        match fetched {
+            Ok(()) if cached.exists() => Ok((cached, None)),
+            Ok(()) => Err(format!("{} has no {} at its root.", url, TEMPLATE_FILE)),
+            // Offline the last fetched templates still work
+            Err(err) if cached.exists() => Ok((cached, Some(format!("{}, using the cached templates.", err)))),
+            Err(err) => Err(err),
+        }
+    }
+}
+
+// One entry per URL, named by its hash so any URL makes a valid file name
+fn cache_path(url: &str) -> Option<PathBuf> {
+    let hash = format!("{:x}", Sha256::digest(url.as_bytes()));
+    Some(config_dir()?.join(CACHE_DIR).join(&hash[..16]))
+}
+
+fn fetch_git(url: &str, dir: &Path) -> Result<(), String> {
+    let mut command = Command::new("git");
+    // There's no terminal to type credentials into, a private repository fails instead of hanging
+    command.env("GIT_TERMINAL_PROMPT", "0");
+    if dir.join(".git").exists() {
+        command.arg("-C").arg(dir).args(["pull", "--ff-only"]);
+    } else {
+        // A clone that failed halfway leaves a folder git refuses to clone into
+        let _ = fs::remove_dir_all(dir);
+        command.args(["clone", "--depth", "1", url]).arg(dir);
+    }
+    run_fetch(command, url)
+}
+
+// curl ships with Windows 10 and later as well as macOS and practically every Linux
+fn fetch_http(url: &str, file: &Path) -> Result<(), String> {
+    if let Some(dir) = file.parent() {
+        fs::create_dir_all(dir).map_err(|err| format!("Failed to create {}: {}", dir.display(), err))?;
+    }
+    // Downloaded next to the cached copy first, so a failed download doesn't replace it with half a file
+    let download = file.with_extension("download");
+    let mut command = Command::new("curl");
+    command
+        .args(["--fail", "--silent", "--show-error", "--location", "--output"])
+        .arg(&download)
+        .arg(url);
+    let fetched = run_fetch(command, url).and_then(|()| fs::rename(&download, file).map_err(|err| format!("Failed to cache {}: {}", url, err)));
+    if fetched.is_err() {
+        let _ = fs::remove_file(&download);
+    }
+    fetched
+}
+
+fn run_fetch(mut command: Command, url: &str) -> Result<(), String> {
+    let program = command.get_program().to_string_lossy().into_owned();
+    let output = command
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|err| format!("Failed to run {} for {}: {}", program, url, err))?;
+    if output.status.success() {
+        Ok(())
+    } else {
+        Err(format!(
+            "Failed to fetch templates from {}: {}",
+            url,
+            String::from_utf8_lossy(&output.stderr).trim()
+        ))
+    }
+}
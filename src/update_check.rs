@@ -0,0 +1,81 @@
+use serde_json::Value;
+use std::process::{Command, Stdio};
+
+// Only published releases, GitHub leaves drafts and pre-releases out of /latest
+const LATEST_RELEASE_URL: &str = "https://api.github.com/repos/julianniewiadomski/gen_gdext/releases/latest";
+
+#[derive(Clone)]
+pub struct Release {
+    pub version: String,
+    // The release page on GitHub
+    pub url: String,
+    // Markdown as written in the release, may be empty
+    pub notes: String,
+}
+
+// None when the latest release isn't newer than this build, fetched with curl like remote templates
+pub fn check_for_update() -> Result<Option<Release>, String> {
+    let output = Command::new("curl")
+        .args(["--fail", "--silent", "--show-error", "--location", "--max-time", "10"])
+        .args(["--header", "Accept: application/vnd.github+json"])
+        // GitHub's API refuses requests without one
+        .args(["--header", concat!("User-Agent: gen_gdext/", env!("CARGO_PKG_VERSION"))])
+        .arg(LATEST_RELEASE_URL)
+        .stdin(Stdio::null())
+        .output()
+        .map_err(|err| format!("Failed to run curl: {}", err))?;
+    if !output.status.success() {
+        return Err(String::from_utf8_lossy(&output.stderr).trim().to_string());
+    }
+
+    let release: Value = serde_json::from_slice(&output.stdout).map_err(|err| format!("Unexpected response from GitHub: {}", err))?;
+    let tag = release["tag_name"].as_str().ok_or("The latest release has no tag.")?;
+    let version = tag.trim_start_matches('v');
+    if !is_newer(version, env!("CARGO_PKG_VERSION")) {
+        return Ok(None);
+    }
+    Ok(Some(Release {
+        version: version.to_string(),
+        url: release["html_url"].as_str().unwrap_or(LATEST_RELEASE_URL).to_string(),
+        notes: release["body"].as_str().unwrap_or_default().trim().to_string(),
+    }))
+}
+
+// "0.10.0" is newer than "0.9.3", "1.0" and "1.0.0" are the same and a version that doesn't parse is never newer
+fn is_newer(version: &str, current: &str) -> bool {
+    let parse = |version: &str| -> Option<Vec<u64>> {
+        // Build metadata and pre-release suffixes don't matter for the comparison, e.g. "1.2.0-beta.1"
+        let release = version.split(['-', '+']).next()?;
+        release.split('.').map(|part| part.parse().ok()).collect()
+    };
+    let (Some(mut version), Some(mut current)) = (parse(version), parse(current)) else {
+        return false;
+    };
+    // Missing parts count as 0
+    let len = version.len().max(current.len());
+    version.resize(len, 0);
+    current.resize(len, 0);
+    version > current
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn newer_versions() {
+        assert!(is_newer("0.10.0", "0.9.3"));
+        assert!(is_newer("1.0.1", "1.0"));
+        assert!(is_newer("2", "1.9.9"));
+        assert!(is_newer("1.2.0-beta.1", "1.1.0"));
+    }
+
+    #[test]
+    fn same_or_older_versions() {
+        assert!(!is_newer("1.0", "1.0.0"));
+        assert!(!is_newer("1.0.0", "1.0"));
+        assert!(!is_newer("0.9.3", "0.10.0"));
+        assert!(!is_newer("latest", "0.1.0"));
+        assert!(!is_newer("1..0", "0.1.0"));
+    }
+}
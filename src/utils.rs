@@ -1,19 +1,1686 @@
-use serde::Deserialize;
+use crate::error::CreateError;
+use crate::gdextension::{quote, unquote, GdExtensionFile};
+use crate::log::{output_level, Log, LogLevel};
+use crate::metadata::{FilePolicy, ProjectMetadata, METADATA_FILE};
+use crate::storage::write_atomic;
+use crate::template_source::TemplateSource;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, BTreeSet};
 use std::fs;
-use std::process::Command;
-use std::sync::{Arc, Mutex};
+use std::io::{BufRead, BufReader, ErrorKind, Read};
+use std::path::{Component, Path, PathBuf};
+use std::process::{Child, Command, ExitStatus, Stdio};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{mpsc, Arc, Mutex};
 use std::thread;
+use std::time::{Duration, Instant, SystemTime};
 
-#[derive(Deserialize, Clone)]
+#[derive(Deserialize, Clone, PartialEq)]
 pub struct ProjectTemplates {
+    #[serde(default)]
+    pub requirements: TemplateRequirements,
+    // Only run when ProjectOptions::template_hooks allows them, a template set may come from anywhere
+    #[serde(default)]
+    pub post_create_hooks: Vec<String>,
     pub gitignore: String,
+    // Extra ignore entries keyed by target platform, e.g. "macos" for macos.debug and macos.release
+    #[serde(default)]
+    pub gitignore_fragments: BTreeMap<String, String>,
+    // The .gitignore at the repository root, template sets without one only get it when a git repository is initialized
+    #[serde(default)]
+    pub project_gitignore: String,
+    #[serde(default)]
+    pub gitattributes: String,
     pub lib_content: String,
+    #[serde(default)]
+    pub lib_content_example: String,
     pub gdextension: String,
     pub cargo_toml: String,
+    #[serde(default)]
+    pub cargo_profile_minimal_size: String,
+    #[serde(default)]
+    pub cargo_profile_release: String,
+    #[serde(default)]
+    pub cargo_profile_custom: String,
+    #[serde(default)]
+    pub cargo_workspace: String,
+    #[serde(default)]
+    pub rustfmt_toml: String,
+    #[serde(default)]
+    pub clippy_toml: String,
+    #[serde(default)]
+    pub cargo_lints: String,
+    #[serde(default)]
+    pub class_definition: String,
+    #[serde(default)]
+    pub class_module: String,
+    #[serde(default)]
+    pub prelude_module: String,
+    #[serde(default)]
+    pub class_registry: String,
+    #[serde(default)]
+    pub main_scene: String,
+    #[serde(default)]
+    pub icon_svg: String,
+    #[serde(default)]
+    pub class_icon: String,
+    #[serde(default)]
+    pub readme: String,
+    // LICENSE texts keyed by SPDX identifier, e.g. "MIT"
+    #[serde(default)]
+    pub licenses: BTreeMap<String, String>,
+    #[serde(default)]
+    pub readme_class_registry: String,
+    #[serde(default)]
+    pub copy_artifacts_script: String,
+    #[serde(default)]
+    pub copy_artifacts_script_windows: String,
+    #[serde(default)]
+    pub github_workflow: String,
+    #[serde(default)]
+    pub export_plugin_cfg: String,
+    #[serde(default)]
+    pub export_plugin_script: String,
+    #[serde(default)]
+    pub export_plugin_hook: String,
+    // Godot export presets keyed by target platform, e.g. "windows" for windows.debug.x86_64 and windows.release.x86_64
+    #[serde(default)]
+    pub export_presets: BTreeMap<String, String>,
+    #[serde(default)]
+    pub vscode_settings: String,
+    #[serde(default)]
+    pub vscode_tasks: String,
+    #[serde(default)]
+    pub vscode_launch: String,
+    #[serde(default)]
+    pub justfile: String,
+    #[serde(default)]
+    pub makefile: String,
+    #[serde(default)]
+    pub addon_plugin_cfg: String,
+    #[serde(default)]
+    pub editor_plugin_cfg: String,
+    #[serde(default)]
+    pub editor_plugin_script: String,
+    #[serde(default)]
+    pub itest_module: String,
+    #[serde(default)]
+    pub itest_scene: String,
+    #[serde(default)]
+    pub itest_script: String,
+    // Found while loading, shown next to the templates status instead of failing the load
+    #[serde(skip)]
+    pub warnings: Vec<TemplateWarning>,
 }
 
-pub fn get_gitignore_content(templates: &ProjectTemplates) -> String {
-    templates.gitignore.clone()
+// Something in templates.yaml that won't do what it looks like, with the line it's on
+#[derive(Clone, PartialEq)]
+pub struct TemplateWarning {
+    pub line: usize,
+    pub field: String,
+    pub kind: TemplateWarningKind,
+}
+
+#[derive(Clone, PartialEq)]
+pub enum TemplateWarningKind {
+    // A key nothing reads
+    UnknownField,
+    // A {placeholder} nothing fills
+    UnknownPlaceholder(String),
+    // {project_name} where the first template sets meant the crate name or the class prefix by it
+    ProjectNameMeaning,
+}
+
+// The templates whose {project_name} stood for the crate name or the class prefix before it meant the project name everywhere
+const PROJECT_NAME_CHANGED_FIELDS: [&str; 4] = ["cargo_toml", "gdextension", "lib_content", "lib_content_example"];
+
+// Every key templates.yaml can have and the placeholders the tool fills in it, the BUILTIN_VARIABLES and user variables come on top
+const TEMPLATE_FIELDS: [(&str, &[&str]); 45] = [
+    ("requirements", &[]),
+    ("post_create_hooks", &["crate_dir"]),
+    ("gitignore", &[]),
+    ("gitignore_fragments", &[]),
+    ("project_gitignore", &[]),
+    ("gitattributes", &[]),
+    (
+        "lib_content",
+        &[
+            "modules",
+            "inline_classes",
+            "extension_attribute",
+            "class_name",
+            "base_class",
+            "base_methods",
+            "class_attributes",
+        ],
+    ),
+    (
+        "lib_content_example",
+        &[
+            "modules",
+            "inline_classes",
+            "extension_attribute",
+            "class_name",
+            "base_class",
+            "base_methods",
+            "class_attributes",
+        ],
+    ),
+    ("gdextension", &[]),
+    ("cargo_toml", &["godot_features", "license"]),
+    ("cargo_profile_minimal_size", &[]),
+    ("cargo_profile_release", &["settings"]),
+    ("cargo_profile_custom", &["profile", "inherits", "settings"]),
+    ("cargo_workspace", &["members"]),
+    ("rustfmt_toml", &[]),
+    ("clippy_toml", &[]),
+    ("cargo_lints", &[]),
+    ("class_definition", &["class_name", "base_class", "base_methods", "class_attributes"]),
+    (
+        "class_module",
+        &["class_definition", "prelude", "class_name", "base_class", "base_methods", "class_attributes"],
+    ),
+    ("prelude_module", &[]),
+    ("class_registry", &[]),
+    ("main_scene", &["scene_root_type"]),
+    ("icon_svg", &[]),
+    ("class_icon", &["icon_color", "icon_initial"]),
+    ("readme", &["layout", "build_steps", "gdextension", "crate_dir"]),
+    ("licenses", &[]),
+    ("readme_class_registry", &["crate_dir"]),
+    ("copy_artifacts_script", &["copy_commands"]),
+    ("copy_artifacts_script_windows", &["copy_commands"]),
+    ("github_workflow", &["matrix", "copy_step", "bin_artifact", "crate_dir"]),
+    ("export_plugin_cfg", &[]),
+    ("export_plugin_script", &[]),
+    ("export_plugin_hook", &["library_copies"]),
+    ("export_presets", &["preset_index"]),
+    ("vscode_settings", &["crate_dir"]),
+    ("vscode_tasks", &["tasks"]),
+    ("vscode_launch", &["godot_executable"]),
+    ("justfile", &["build_recipe_names", "build_recipes"]),
+    ("makefile", &["build_recipe_names", "build_recipes"]),
+    ("addon_plugin_cfg", &["class_name"]),
+    ("editor_plugin_cfg", &[]),
+    ("editor_plugin_script", &["crate_dir", "cargo", "cargo_args"]),
+    ("itest_module", &[]),
+    ("itest_scene", &[]),
+    ("itest_script", &["build_steps"]),
+];
+
+pub const EDITOR_PLUGIN_DIR: &str = "addons/rust_rebuild";
+
+pub const EXPORT_PLUGIN_DIR: &str = "addons/rust_export";
+
+pub const TEMPLATE_FILE: &str = "templates.yaml";
+
+// Declares every class module of the multi-class layouts, one `class!` line per class, relative to the crate
+const CLASS_REGISTRY_FILE: &str = "src/registry.rs";
+
+pub const GODOT_EXECUTABLE: &str = "godot";
+
+pub const CODE_EDITOR: &str = "code";
+
+pub const DEFAULT_GIT_COMMIT_MESSAGE: &str = "Initial commit";
+
+pub const DEFAULT_RELOAD_ITERATIONS: u32 = 5;
+
+pub const KNOWN_TARGETS: [&str; 6] = [
+    "linux.debug.x86_64",
+    "linux.release.x86_64",
+    "windows.debug.x86_64",
+    "windows.release.x86_64",
+    "macos.debug",
+    "macos.release",
+];
+
+// Names the editor is installed under on the PATH, the official downloads and package managers differ
+const GODOT_PATH_NAMES: [&str; 3] = ["godot", "godot4", "Godot"];
+
+// The first Godot found on the PATH or in a common install location, None if there is none
+pub fn detect_godot_executable() -> Option<String> {
+    let extension = if cfg!(windows) { ".exe" } else { "" };
+    let on_path = std::env::var_os("PATH")
+        .into_iter()
+        .flat_map(|paths| std::env::split_paths(&paths).collect::<Vec<_>>());
+    let mut candidates: Vec<PathBuf> = on_path
+        .flat_map(|dir| GODOT_PATH_NAMES.map(|name| dir.join(format!("{}{}", name, extension))))
+        .collect();
+
+    let home = std::env::var_os(if cfg!(windows) { "USERPROFILE" } else { "HOME" }).map(PathBuf::from);
+    if cfg!(windows) {
+        let local_app_data = std::env::var_os("LOCALAPPDATA").map(PathBuf::from);
+        candidates.extend(local_app_data.map(|dir| dir.join("Programs/Godot/Godot.exe")));
+        candidates.extend(home.map(|home| home.join("scoop/apps/godot/current/godot.exe")));
+        candidates.push(PathBuf::from("C:/Program Files/Godot/Godot.exe"));
+        candidates.push(PathBuf::from(
+            "C:/Program Files (x86)/Steam/steamapps/common/Godot Engine/godot.windows.opt.tools.64.exe",
+        ));
+    } else if cfg!(target_os = "macos") {
+        candidates.push(PathBuf::from("/Applications/Godot.app/Contents/MacOS/Godot"));
+        candidates.push(PathBuf::from("/Applications/Godot_mono.app/Contents/MacOS/Godot"));
+        candidates.push(PathBuf::from("/opt/homebrew/bin/godot"));
+        if let Some(home) = home {
+            candidates.push(home.join("Applications/Godot.app/Contents/MacOS/Godot"));
+            candidates.push(home.join("Library/Application Support/Steam/steamapps/common/Godot Engine/Godot.app/Contents/MacOS/Godot"));
+        }
+    } else {
+        candidates.push(PathBuf::from("/var/lib/flatpak/exports/bin/org.godotengine.Godot"));
+        candidates.push(PathBuf::from("/snap/bin/godot-4"));
+        if let Some(home) = home {
+            candidates.push(home.join(".local/share/flatpak/exports/bin/org.godotengine.Godot"));
+            candidates.push(home.join(".local/share/Steam/steamapps/common/Godot Engine/godot.x11.opt.tools.64"));
+        }
+    }
+
+    candidates.into_iter().find(|path| path.is_file()).map(|path| path.display().to_string())
+}
+
+// One row of the prerequisite check, `hint` says how to fix a failed one
+#[derive(Clone)]
+pub struct PrerequisiteCheck {
+    pub name: String,
+    pub passed: bool,
+    pub detail: String,
+    pub hint: String,
+}
+
+// Runs the external tools creation and builds rely on, blocks until all of them answered
+pub fn check_prerequisites(options: &ProjectOptions, template_source: &TemplateSource) -> Vec<PrerequisiteCheck> {
+    let mut checks = vec![
+        check_tool(
+            "cargo",
+            options.cargo_program(),
+            "Install Rust from https://rustup.rs or set the cargo executable.",
+        ),
+        check_tool("rustc", "rustc", "Install Rust from https://rustup.rs."),
+    ];
+
+    let required = required_rust_targets(options);
+    match options.cross_builder {
+        CrossBuilder::Cargo => {}
+        _ if required.is_empty() => {}
+        CrossBuilder::Cross => checks.push(check_tool("cross", "cross", "cargo install cross, it also needs Docker or Podman.")),
+        CrossBuilder::Zigbuild => checks.push(check_tool(
+            "cargo-zigbuild",
+            "cargo-zigbuild",
+            "cargo install cargo-zigbuild, it also needs zig on the PATH.",
+        )),
+    }
+    if !required.is_empty() {
+        checks.push(match installed_rust_targets() {
+            Some(installed) => {
+                let missing: Vec<&String> = required.iter().filter(|triple| !installed.contains(triple)).collect();
+                PrerequisiteCheck {
+                    name: "rustup targets".to_string(),
+                    passed: missing.is_empty(),
+                    detail: if missing.is_empty() {
+                        format!("{} installed", required.join(", "))
+                    } else {
+                        format!("{} missing", missing.iter().map(|triple| triple.as_str()).collect::<Vec<_>>().join(", "))
+                    },
+                    hint: format!(
+                        "rustup target add {}",
+                        missing.iter().map(|triple| triple.as_str()).collect::<Vec<_>>().join(" ")
+                    ),
+                }
+            }
+            None => PrerequisiteCheck {
+                name: "rustup targets".to_string(),
+                passed: false,
+                detail: "rustup is not available to list the installed targets".to_string(),
+                hint: format!("Install rustup, then rustup target add {}", required.join(" ")),
+            },
+        });
+    }
+
+    checks.push(check_tool(
+        "Godot",
+        options.godot_program(),
+        "Install Godot 4 or set the Godot executable, Detect searches the usual install locations.",
+    ));
+
+    // The templates the window uses, a remote source is checked in its cache like it is loaded
+    let templates = template_source.resolve(false).and_then(|(path, _)| {
+        let path = path.to_string_lossy().into_owned();
+        let content = fs::read_to_string(&path).map_err(|err| format!("Failed to read {}: {}", path, err))?;
+        parse_templates(&path, &content).map(|_| path)
+    });
+    let hint = if template_source.is_remote() {
+        "Check the template source URL, Refresh templates fetches it again.".to_string()
+    } else {
+        format!("Fix {} or restore it from the release archive.", TEMPLATE_FILE)
+    };
+    checks.push(PrerequisiteCheck {
+        name: "templates".to_string(),
+        passed: templates.is_ok(),
+        detail: templates.map_or_else(|err| err, |path| format!("{} parses", path)),
+        hint,
+    });
+
+    checks
+}
+
+fn check_tool(name: &str, program: &str, hint: &str) -> PrerequisiteCheck {
+    let output = Command::new(program).arg("--version").stdin(Stdio::null()).stderr(Stdio::null()).output();
+    let (passed, detail) = match output {
+        Ok(output) if output.status.success() => (
+            true,
+            String::from_utf8_lossy(&output.stdout).lines().next().unwrap_or_default().trim().to_string(),
+        ),
+        Ok(output) => (false, format!("{} --version exited with {}", program, output.status)),
+        Err(err) => (false, format!("{} could not be started: {}", program, err)),
+    };
+    PrerequisiteCheck {
+        name: name.to_string(),
+        passed,
+        detail,
+        hint: hint.to_string(),
+    }
+}
+
+// The rustc triples a Godot target is built for, macOS libraries are universal and need both architectures
+fn rust_target_triples(target: &str) -> Vec<String> {
+    let mut parts = target.split('.');
+    let platform = parts.next().unwrap_or_default();
+    let arch = match parts.nth(1) {
+        Some("arm64") => "aarch64",
+        Some("x86_32") => "i686",
+        _ => "x86_64",
+    };
+    match platform {
+        "linux" => vec![format!("{}-unknown-linux-gnu", arch)],
+        "windows" => vec![format!("{}-pc-windows-gnu", arch)],
+        "macos" => vec!["x86_64-apple-darwin".to_string(), "aarch64-apple-darwin".to_string()],
+        _ => Vec::new(),
+    }
+}
+
+// Only targets of another platform need a rustup target, the host one comes with the toolchain
+fn required_rust_targets(options: &ProjectOptions) -> Vec<String> {
+    let host_triple = detect_host_triple().unwrap_or_default();
+    let mut required: Vec<String> = options
+        .targets
+        .iter()
+        .filter(|target| !is_native_target(target, &host_triple))
+        .flat_map(|target| rust_target_triples(target))
+        .collect();
+    required.sort();
+    required.dedup();
+    required
+}
+
+// Required triples rustup doesn't have yet, empty without rustup since there is nothing to install them with
+pub fn missing_rust_targets(options: &ProjectOptions) -> Vec<String> {
+    let Some(installed) = installed_rust_targets() else {
+        return Vec::new();
+    };
+    required_rust_targets(options)
+        .into_iter()
+        .filter(|triple| !installed.contains(triple))
+        .collect()
+}
+
+pub fn install_rust_targets(triples: &[String], log: &Arc<Mutex<Log>>, cancel: &AtomicBool) -> Result<(), CreateError> {
+    log.lock().unwrap().info(&format!("Installing rustup targets {}...", triples.join(", ")));
+
+    let mut command = Command::new("rustup");
+    command.args(["target", "add"]).args(triples);
+    match run_streamed(command, log, cancel) {
+        Ok(status) if status.success() => {
+            log.lock().unwrap().info("Installed the rustup targets.");
+            Ok(())
+        }
+        Ok(_) => Err(CreateError::RustupFailed),
+        Err(err) if err.kind() == ErrorKind::Interrupted => Err(CreateError::Cancelled),
+        Err(err) => Err(CreateError::SpawnRustup(err)),
+    }
+}
+
+fn installed_rust_targets() -> Option<Vec<String>> {
+    let output = Command::new("rustup")
+        .args(["target", "list", "--installed"])
+        .stderr(Stdio::null())
+        .output()
+        .ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).lines().map(|line| line.trim().to_string()).collect())
+}
+
+// The locale the user runs the tool in as Godot writes it, e.g. "de_DE" from LANG=de_DE.UTF-8
+pub fn detect_system_locale() -> Option<String> {
+    let value = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .into_iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|value| !value.trim().is_empty())?;
+    let locale = value.split(['.', '@']).next()?.trim().replace('-', "_");
+    (is_valid_locale(&locale) && locale != "C" && locale != "POSIX").then_some(locale)
+}
+
+// Language code with optional script, country and variant parts, e.g. "pt", "pt_BR" or "zh_Hans_CN"
+pub fn is_valid_locale(locale: &str) -> bool {
+    let mut parts = locale.split('_');
+    parts
+        .next()
+        .is_some_and(|language| (2..=3).contains(&language.len()) && language.chars().all(|c| c.is_ascii_alphabetic()))
+        && parts.all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_alphanumeric()))
+}
+
+// The rustc host triple, e.g. "x86_64-unknown-linux-gnu", None without a working rustc
+pub fn detect_host_triple() -> Option<String> {
+    let output = Command::new("rustc").arg("-vV").stderr(Stdio::null()).output().ok()?;
+    String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .find_map(|line| line.strip_prefix("host: "))
+        .map(|host| host.trim().to_string())
+}
+
+// Whether the host toolchain builds a target's library as is, other targets need a rustup target and a linker
+pub fn is_native_target(target: &str, host_triple: &str) -> bool {
+    let mut parts = target.split('.');
+    let os_matches = match parts.next() {
+        Some("linux") => host_triple.contains("-linux"),
+        Some("windows") => host_triple.contains("-windows"),
+        Some("macos") => host_triple.contains("-apple-darwin"),
+        _ => false,
+    };
+    // macOS targets carry no architecture, their libraries are universal
+    let arch_matches = parts.nth(1).is_none_or(|arch| host_triple.starts_with(arch));
+    os_matches && arch_matches
+}
+
+// Optional features of the godot crate offered for the generated Cargo.toml
+pub const KNOWN_GODOT_FEATURES: [&str; 4] = ["experimental-threads", "lazy-function-tables", "api-custom", "serde"];
+
+// Crates offered for the generated Cargo.toml: name, the version used when crates.io can't be asked and its features
+pub const KNOWN_DEPENDENCIES: [(&str, &str, &[&str]); 7] = [
+    ("serde", "1", &["derive"]),
+    ("serde_json", "1", &[]),
+    ("rand", "0.8", &[]),
+    ("anyhow", "1", &[]),
+    ("thiserror", "1", &[]),
+    ("tracing", "0.1", &[]),
+    ("tracing-subscriber", "0.3", &[]),
+];
+
+#[derive(Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct ProjectOptions {
+    pub godot_version: GodotVersion,
+    pub reloadable: bool,
+    pub targets: Vec<String>,
+    pub precompile_lib: bool,
+    pub sign_libraries: bool,
+    pub minisign_key: String,
+    pub existing_project: ExistingProject,
+    pub editor_plugin: bool,
+    pub minimal_size: bool,
+    pub release_profile: ReleaseProfile,
+    pub env: BTreeMap<String, String>,
+    pub renderer: Renderer,
+    pub display_name: String,
+    // Empty derives them from the project name, see ProjectNames
+    pub crate_name: String,
+    pub class_prefix: String,
+    pub base_class: BaseClass,
+    pub classes: Vec<ClassSpec>,
+    pub flavor: ProjectFlavor,
+    pub layout: CrateLayout,
+    pub lib_scaffold: LibScaffold,
+    pub double_precision: bool,
+    pub prelude_module: bool,
+    // rustfmt.toml, clippy.toml and a [lints] table for the crate
+    pub code_style: bool,
+    // Tests running inside a headless Godot, see itest_module in templates.yaml
+    pub itest: bool,
+    // An editor icon per Rust class under addons/<name>/icons/ and the [icons] section pointing at them
+    pub class_icons: bool,
+    pub godot_features: Vec<String>,
+    // Names from KNOWN_DEPENDENCIES added to the crate's [dependencies]
+    pub dependencies: Vec<String>,
+    // Filled from crates.io while creating, dependencies missing here get their fallback version
+    #[serde(skip)]
+    pub dependency_versions: BTreeMap<String, String>,
+    pub crate_location: CrateLocation,
+    pub git_init: bool,
+    pub git_commit_message: String,
+    pub license: License,
+    pub author: String,
+    pub library_layout: LibraryLayout,
+    pub readme: bool,
+    pub github_workflow: bool,
+    pub export_presets: bool,
+    pub task_runner: TaskRunner,
+    pub vscode: bool,
+    pub cargo: String,
+    pub cargo_args: Vec<String>,
+    pub godot: String,
+    pub open_in_godot: bool,
+    pub editor: String,
+    pub open_in_editor: bool,
+    pub cross_builder: CrossBuilder,
+    // Written as the project's fallback locale, empty leaves Godot's default of "en"
+    pub locale: String,
+    // Empty uses gdext's default, anything else is also passed to #[gdextension] in lib.rs
+    pub entry_symbol: String,
+    pub compatibility_maximum: Option<GodotVersion>,
+    // Keyed by target, replaces the path the .gdextension would load that target's library from
+    pub library_paths: BTreeMap<String, String>,
+    // Cargo profile per target, e.g. "dist" for release targets, targets left out build with the debug or release in their name
+    pub target_profiles: BTreeMap<String, String>,
+    // Relative to the crate like cargo's build.target-dir, empty keeps the crate's own target/
+    pub target_dir: String,
+    // Further extension crates, each a workspace member in a folder of its name inside the crate with its own .gdextension
+    pub extension_crates: Vec<String>,
+    // User-defined {name} placeholders filled into every generated file alongside the built-in ones
+    pub template_variables: BTreeMap<String, String>,
+    // Shell commands run in the new project after the template set's hooks
+    pub post_create_hooks: Vec<String>,
+    pub template_hooks: bool,
+}
+
+pub const DEFAULT_TARGET_DIR: &str = "target";
+
+// The symbol #[gdextension] exports unless it's told otherwise
+pub const DEFAULT_ENTRY_SYMBOL: &str = "gdext_rust_init";
+
+pub const OPT_LEVELS: [&str; 6] = ["0", "1", "2", "3", "s", "z"];
+pub const LTO_MODES: [&str; 3] = ["false", "thin", "fat"];
+pub const STRIP_MODES: [&str; 3] = ["none", "debuginfo", "symbols"];
+
+// Hand-picked [profile.release] settings, None keeps cargo's default, the minimal size preset takes precedence
+#[derive(Clone, Default, PartialEq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct ReleaseProfile {
+    pub opt_level: Option<String>,
+    pub lto: Option<String>,
+    pub codegen_units: Option<u32>,
+    pub strip: Option<String>,
+    pub panic_abort: bool,
+}
+
+impl ReleaseProfile {
+    pub fn is_empty(&self) -> bool {
+        self.opt_level.is_none() && self.lto.is_none() && self.codegen_units.is_none() && self.strip.is_none() && !self.panic_abort
+    }
+
+    // The body of the [profile.release] table, numbers and `false` unquoted like cargo documents them
+    fn settings(&self) -> String {
+        let mut lines = Vec::new();
+        if let Some(opt_level) = &self.opt_level {
+            match opt_level.parse::<u32>() {
+                Ok(level) => lines.push(format!("opt-level = {}", level)),
+                Err(_) => lines.push(format!("opt-level = \"{}\"", opt_level)),
+            }
+        }
+        if let Some(lto) = &self.lto {
+            match lto.as_str() {
+                "false" => lines.push("lto = false".to_string()),
+                lto => lines.push(format!("lto = \"{}\"", lto)),
+            }
+        }
+        if let Some(codegen_units) = self.codegen_units {
+            lines.push(format!("codegen-units = {}", codegen_units));
+        }
+        if self.panic_abort {
+            lines.push("panic = \"abort\"".to_string());
+        }
+        if let Some(strip) = &self.strip {
+            lines.push(format!("strip = \"{}\"", strip));
+        }
+        lines.join("\n")
+    }
+
+    fn problems(&self) -> Vec<String> {
+        let mut problems = Vec::new();
+        let checks = [
+            ("opt-level", &self.opt_level, &OPT_LEVELS[..]),
+            ("lto", &self.lto, &LTO_MODES[..]),
+            ("strip", &self.strip, &STRIP_MODES[..]),
+        ];
+        for (key, value, allowed) in checks {
+            if let Some(value) = value.as_deref().filter(|value| !allowed.contains(value)) {
+                problems.push(format!("'{}' is not a valid {}, expected one of {}.", value, key, allowed.join(", ")));
+            }
+        }
+        if self.codegen_units == Some(0) {
+            problems.push("codegen-units has to be at least 1.".to_string());
+        }
+        problems
+    }
+}
+
+// What builds the libraries of another platform, the host's own targets always build with plain cargo
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrossBuilder {
+    #[default]
+    Cargo,
+    Cross,
+    Zigbuild,
+}
+
+// A selected target's progress through precompilation
+#[derive(Clone, PartialEq)]
+pub enum TargetStatus {
+    Pending,
+    Building,
+    Built,
+    Failed(String),
+}
+
+impl std::fmt::Display for TargetStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TargetStatus::Pending => write!(f, "pending"),
+            TargetStatus::Building => write!(f, "building"),
+            TargetStatus::Built => write!(f, "built"),
+            TargetStatus::Failed(reason) => write!(f, "failed: {}", reason),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct TargetBuild {
+    pub target: String,
+    pub status: TargetStatus,
+    // Packages cargo reported as compiled or fresh, out of `crates_total`, which is 0 when cargo metadata failed
+    pub crates_built: usize,
+    pub crates_total: usize,
+}
+
+// The parts of cargo's JSON messages and metadata the build progress needs, serde skips the rest
+#[derive(Deserialize)]
+struct CargoMessage {
+    reason: String,
+    #[serde(default)]
+    package_id: String,
+}
+
+#[derive(Deserialize)]
+struct CargoMetadata {
+    resolve: Option<CargoResolve>,
+}
+
+#[derive(Deserialize)]
+struct CargoResolve {
+    nodes: Vec<serde_json::Value>,
+}
+
+// Which lib.rs template the crate starts from
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LibScaffold {
+    #[default]
+    Minimal,
+    Example,
+}
+
+// The command runner file generated with per-target build recipes
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TaskRunner {
+    #[default]
+    None,
+    Just,
+    Make,
+}
+
+// Where the .gdextension loads the libraries from
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LibraryLayout {
+    // Straight out of cargo's target dir
+    #[default]
+    TargetDir,
+    // bin/<platform>/ inside the project, filled by the generated copy scripts
+    Bin,
+    // The editor loads from the target dir, exports from bin/, which an export plugin fills before packing
+    Split,
+}
+
+impl LibraryLayout {
+    pub fn uses_bin(self) -> bool {
+        matches!(self, LibraryLayout::Bin | LibraryLayout::Split)
+    }
+}
+
+// Where the Rust crate lives relative to the Godot project
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrateLocation {
+    // rust/ inside the project
+    #[default]
+    Inside,
+    // <name>_rust/ next to the project, for keeping engine content and native code in separate repos
+    Sibling,
+    // <name>/godot/ and <name>/rust/ side by side in one repository, the structure the godot-rust book uses
+    Root,
+}
+
+// The Godot project's folder below <name>/ with the root layout
+const ROOT_LAYOUT_GODOT_DIR: &str = "godot";
+
+// The crate directory relative to the project directory
+pub fn crate_relative_dir(project_name: &str, location: CrateLocation) -> String {
+    match location {
+        CrateLocation::Inside => "rust".to_string(),
+        CrateLocation::Sibling => format!("../{}_rust", project_name),
+        CrateLocation::Root => "../rust".to_string(),
+    }
+}
+
+// The directory holding project.godot for a new project
+pub fn project_dir(project_name: &str, location: CrateLocation) -> PathBuf {
+    match location {
+        CrateLocation::Inside | CrateLocation::Sibling => PathBuf::from(project_name),
+        CrateLocation::Root => Path::new(project_name).join(ROOT_LAYOUT_GODOT_DIR),
+    }
+}
+
+// The folders in the working directory holding a project.godot, the projects Rust can be added to
+pub fn find_godot_projects() -> Vec<String> {
+    let mut projects: Vec<String> = fs::read_dir(".")
+        .into_iter()
+        .flatten()
+        .flatten()
+        .filter(|entry| entry.path().join("project.godot").is_file())
+        .filter_map(|entry| entry.file_name().into_string().ok())
+        .collect();
+    projects.sort();
+    projects
+}
+
+// Existing projects don't record their layout either, a project.godot only under godot/ means the root layout
+pub fn find_project_dir(project_name: &str) -> PathBuf {
+    let root_layout = project_dir(project_name, CrateLocation::Root);
+    if !Path::new(project_name).join("project.godot").exists() && root_layout.join("project.godot").exists() {
+        root_layout
+    } else {
+        PathBuf::from(project_name)
+    }
+}
+
+// The crate directory relative to the working directory, "game/../game_rust" -> "game_rust"
+pub fn crate_path(project_name: &str, location: CrateLocation) -> String {
+    let path = format!(
+        "{}/{}",
+        project_dir(project_name, location).display(),
+        crate_relative_dir(project_name, location)
+    );
+    normalize_relative_path(&path).unwrap_or(path)
+}
+
+// Files belonging at the top of the repository sit one level above project.godot with the root layout
+fn repository_prefix(location: CrateLocation) -> &'static str {
+    match location {
+        CrateLocation::Inside | CrateLocation::Sibling => "",
+        CrateLocation::Root => "../",
+    }
+}
+
+// Existing projects don't record where their crate is, a sibling crate is only picked when there's no rust/
+fn find_crate_dir(project_name: &str) -> String {
+    let project_dir = find_project_dir(project_name);
+    if project_dir != Path::new(project_name) {
+        return crate_relative_dir(project_name, CrateLocation::Root);
+    }
+    let sibling = crate_relative_dir(project_name, CrateLocation::Sibling);
+    if !project_dir.join("rust").exists() && project_dir.join(&sibling).join("Cargo.toml").exists() {
+        sibling
+    } else {
+        crate_relative_dir(project_name, CrateLocation::Inside)
+    }
+}
+
+// An existing sibling crate counts as an existing project, the project directory alone doesn't cover it
+pub fn sibling_crate_exists(project_name: &str, options: &ProjectOptions) -> bool {
+    options.crate_location == CrateLocation::Sibling && Path::new(&format!("{}_rust", project_name)).exists()
+}
+
+// The crate directory as an absolute path with the ".." of a sibling crate resolved
+fn absolute_crate_dir(project_dir: &Path, crate_dir: &str) -> Option<PathBuf> {
+    let mut path = std::env::current_dir().ok()?.join(project_dir);
+    for component in Path::new(crate_dir).components() {
+        match component {
+            Component::ParentDir => {
+                path.pop();
+            }
+            component => path.push(component),
+        }
+    }
+    Some(path)
+}
+
+// What a template set declares it works with, checked when it's loaded and against the chosen options
+#[derive(Clone, Default, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct TemplateRequirements {
+    // The oldest gen_gdext that fills every placeholder the templates use
+    pub min_tool_version: Option<String>,
+    pub min_godot: Option<GodotVersion>,
+    // Without a patch number every patch release of that minor version is included
+    pub max_godot: Option<GodotVersion>,
+    // The gdext release or branch the generated code is written against, shown but not checked
+    pub gdext: Option<String>,
+}
+
+pub const TOOL_VERSION: &str = env!("CARGO_PKG_VERSION");
+
+// "0.1.0" -> (0, 1, 0), missing parts count as 0 and a pre-release suffix is ignored
+fn parse_tool_version(version: &str) -> Option<(u32, u32, u32)> {
+    let release = version.trim().trim_start_matches(['v', 'V']).split(['-', '+']).next()?;
+    let mut parts = release.split('.');
+    let mut next = || parts.next().map_or(Some(0), |part| part.parse().ok());
+    let version = (next()?, next()?, next()?);
+    parts.next().is_none().then_some(version)
+}
+
+impl TemplateRequirements {
+    fn check_tool_version(&self, path: &str) -> Result<(), String> {
+        let Some(required) = &self.min_tool_version else {
+            return Ok(());
+        };
+        let minimum = parse_tool_version(required).ok_or_else(|| format!("{} requires gen_gdext '{}', which is not a version.", path, required))?;
+        if parse_tool_version(TOOL_VERSION).is_some_and(|current| current < minimum) {
+            return Err(format!("{} needs gen_gdext {} or newer, this is {}.", path, required.trim(), TOOL_VERSION));
+        }
+        Ok(())
+    }
+
+    pub fn godot_range_problem(&self, version: GodotVersion) -> Option<String> {
+        let below = self.min_godot.is_some_and(|min| version.sort_key() < min.sort_key());
+        let above = self.max_godot.is_some_and(|max| version.exceeds(max));
+        if !below && !above {
+            return None;
+        }
+        let range = match (self.min_godot, self.max_godot) {
+            (Some(min), Some(max)) => format!("Godot {} to {}", min, max),
+            (Some(min), None) => format!("Godot {} and newer", min),
+            (None, Some(max)) => format!("Godot {} and older", max),
+            (None, None) => unreachable!(),
+        };
+        Some(format!("The templates support {}, Godot {} is selected.", range, version))
+    }
+}
+
+// A Godot 4 release as major.minor[.patch], parsed leniently from what users type, e.g. "v4.3" or "4"
+#[derive(Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(try_from = "String")]
+pub struct GodotVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: Option<u32>,
+}
+
+pub const DEFAULT_GODOT_VERSION: GodotVersion = GodotVersion {
+    major: 4,
+    minor: 2,
+    patch: None,
+};
+
+impl GodotVersion {
+    // Godot tags the project with its major.minor version, e.g. "4.2" for 4.2.1
+    fn feature_tag(self) -> String {
+        format!("{}.{}", self.major, self.minor)
+    }
+
+    fn sort_key(self) -> (u32, u32, u32) {
+        (self.major, self.minor, self.patch.unwrap_or(0))
+    }
+
+    // Newer than an upper bound, a bound without a patch number includes every patch release of its minor version
+    fn exceeds(self, max: GodotVersion) -> bool {
+        match max.patch {
+            Some(_) => self.sort_key() > max.sort_key(),
+            None => (self.major, self.minor) > (max.major, max.minor),
+        }
+    }
+}
+
+impl Default for GodotVersion {
+    fn default() -> Self {
+        DEFAULT_GODOT_VERSION
+    }
+}
+
+impl std::fmt::Display for GodotVersion {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}.{}", self.major, self.minor)?;
+        if let Some(patch) = self.patch {
+            write!(f, ".{}", patch)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::str::FromStr for GodotVersion {
+    type Err = String;
+
+    fn from_str(input: &str) -> Result<Self, Self::Err> {
+        let invalid = || format!("'{}' is not a Godot version, expected major.minor[.patch] like 4.2 or 4.2.1.", input.trim());
+        let trimmed = input.trim();
+        let numbers = trimmed.strip_prefix(['v', 'V']).unwrap_or(trimmed);
+        let parts = numbers
+            .split('.')
+            .map(|part| part.parse::<u32>().map_err(|_| invalid()))
+            .collect::<Result<Vec<u32>, String>>()?;
+        let version = match parts[..] {
+            [major] => GodotVersion { major, minor: 0, patch: None },
+            [major, minor] => GodotVersion { major, minor, patch: None },
+            [major, minor, patch] => GodotVersion {
+                major,
+                minor,
+                patch: Some(patch),
+            },
+            _ => return Err(invalid()),
+        };
+        if version.major != 4 {
+            return Err(format!("Godot {} isn't supported, GDExtension projects need Godot 4.", version));
+        }
+        Ok(version)
+    }
+}
+
+impl TryFrom<String> for GodotVersion {
+    type Error = String;
+
+    fn try_from(input: String) -> Result<Self, Self::Error> {
+        input.parse()
+    }
+}
+
+// Where the additional classes go in the generated crate
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CrateLayout {
+    // Every class in lib.rs
+    Flat,
+    #[default]
+    Classes,
+    // gameplay/, systems/ and ui/ folders, picked by base class
+    Domains,
+}
+
+impl CrateLayout {
+    fn module_dir(self, base_class: BaseClass) -> Option<&'static str> {
+        match self {
+            CrateLayout::Flat => None,
+            CrateLayout::Classes => Some("classes"),
+            CrateLayout::Domains => Some(base_class.domain()),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ProjectFlavor {
+    #[default]
+    Game,
+    // An editor addon under addons/<name>, its main class is an EditorPlugin
+    Addon,
+}
+
+impl ProjectOptions {
+    pub fn cargo_program(&self) -> &str {
+        if self.cargo.trim().is_empty() {
+            "cargo"
+        } else {
+            self.cargo.trim()
+        }
+    }
+
+    pub fn godot_program(&self) -> &str {
+        if self.godot.trim().is_empty() {
+            GODOT_EXECUTABLE
+        } else {
+            self.godot.trim()
+        }
+    }
+
+    pub fn target_dir(&self) -> &str {
+        if self.target_dir.trim().is_empty() {
+            DEFAULT_TARGET_DIR
+        } else {
+            self.target_dir.trim()
+        }
+    }
+
+    pub fn has_custom_target_dir(&self) -> bool {
+        self.target_dir() != DEFAULT_TARGET_DIR
+    }
+
+    fn target_dir_in_crate(&self) -> bool {
+        let target_dir = Path::new(self.target_dir());
+        target_dir.is_relative() && !target_dir.components().any(|component| component == Component::ParentDir)
+    }
+
+    pub fn entry_symbol(&self) -> &str {
+        if self.entry_symbol.trim().is_empty() {
+            DEFAULT_ENTRY_SYMBOL
+        } else {
+            self.entry_symbol.trim()
+        }
+    }
+
+    // Named like the folder under the target dir it builds into, so cargo's dev profile is "debug"
+    pub fn target_profile(&self, target: &str) -> Option<String> {
+        let default = default_profile(target)?;
+        Some(match self.target_profiles.get(target).map(|profile| profile.trim()) {
+            Some("dev") => "debug".to_string(),
+            Some(profile) if !profile.is_empty() => profile.to_string(),
+            _ => default.to_string(),
+        })
+    }
+
+    // The command line the generated recipes use, the configured cargo and extra flags included
+    pub fn cargo_build_command(&self, crate_dir: &str, profile: &str) -> String {
+        let mut command = format!("{} build --manifest-path {}/Cargo.toml", self.cargo_program(), crate_dir);
+        for arg in profile_args(profile) {
+            command.push(' ');
+            command.push_str(&arg);
+        }
+        // Run from outside the crate cargo doesn't see its .cargo/config.toml, so the target dir is passed along
+        if self.has_custom_target_dir() {
+            command.push_str(&format!(" --target-dir {}", target_dir_path(crate_dir, self.target_dir())));
+        }
+        for arg in &self.cargo_args {
+            command.push(' ');
+            command.push_str(arg);
+        }
+        command
+    }
+
+    pub fn main_base_class(&self) -> BaseClass {
+        match self.flavor {
+            ProjectFlavor::Game => self.base_class,
+            ProjectFlavor::Addon => BaseClass::EditorPlugin,
+        }
+    }
+
+    // The godot crate features the generated Cargo.toml enables
+    fn godot_crate_features(&self) -> Vec<String> {
+        let mut features = self.godot_features.clone();
+        if self.double_precision {
+            features.push("double-precision".to_string());
+        }
+        features
+    }
+}
+
+// An extra class generated into its own module next to lib.rs
+#[derive(Clone, Default, PartialEq, Deserialize)]
+pub struct ClassSpec {
+    pub name: String,
+    #[serde(default)]
+    pub base_class: BaseClass,
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub enum BaseClass {
+    #[default]
+    Node,
+    Node2D,
+    Node3D,
+    Control,
+    CharacterBody2D,
+    CharacterBody3D,
+    RigidBody2D,
+    RigidBody3D,
+    Area2D,
+    Area3D,
+    Resource,
+    RefCounted,
+    EditorPlugin,
+}
+
+impl BaseClass {
+    pub const ALL: [BaseClass; 13] = [
+        BaseClass::Node,
+        BaseClass::Node2D,
+        BaseClass::Node3D,
+        BaseClass::Control,
+        BaseClass::CharacterBody2D,
+        BaseClass::CharacterBody3D,
+        BaseClass::RigidBody2D,
+        BaseClass::RigidBody3D,
+        BaseClass::Area2D,
+        BaseClass::Area3D,
+        BaseClass::Resource,
+        BaseClass::RefCounted,
+        BaseClass::EditorPlugin,
+    ];
+
+    // Resources aren't nodes and an editor plugin in a running scene does nothing
+    pub fn can_be_scene_root(self) -> bool {
+        !matches!(self, BaseClass::Resource | BaseClass::RefCounted | BaseClass::EditorPlugin)
+    }
+
+    // The colors Godot's editor icons use for 2D, 3D and Control nodes
+    fn icon_color(self) -> &'static str {
+        match self {
+            BaseClass::Control => "#8eef97",
+            BaseClass::Node2D | BaseClass::CharacterBody2D | BaseClass::RigidBody2D | BaseClass::Area2D => "#8da5f3",
+            BaseClass::Node3D | BaseClass::CharacterBody3D | BaseClass::RigidBody3D | BaseClass::Area3D => "#fc7f7f",
+            BaseClass::Node | BaseClass::Resource | BaseClass::RefCounted | BaseClass::EditorPlugin => "#e0e0e0",
+        }
+    }
+
+    fn domain(self) -> &'static str {
+        match self {
+            BaseClass::Control => "ui",
+            BaseClass::Node2D
+            | BaseClass::Node3D
+            | BaseClass::CharacterBody2D
+            | BaseClass::CharacterBody3D
+            | BaseClass::RigidBody2D
+            | BaseClass::RigidBody3D
+            | BaseClass::Area2D
+            | BaseClass::Area3D => "gameplay",
+            BaseClass::Node | BaseClass::Resource | BaseClass::RefCounted | BaseClass::EditorPlugin => "systems",
+        }
+    }
+
+    fn class_attributes(self) -> String {
+        match self {
+            // Editor plugins only do anything when they also run inside the editor
+            BaseClass::EditorPlugin => format!("tool, init, base={}", self),
+            _ => format!("init, base={}", self),
+        }
+    }
+
+    // Stub overrides for the I* trait impl, indented to sit inside it, with the class name already in its messages
+    fn methods(self, class_name: &str) -> String {
+        match self {
+            BaseClass::Node | BaseClass::Node2D | BaseClass::Node3D | BaseClass::Control | BaseClass::Area2D | BaseClass::Area3D => format!(
+                "    fn ready(&mut self) {{\n        godot_print!(\"{} is ready.\");\n    }}\n\n    fn process(&mut self, _delta: f64) {{}}\n",
+                class_name
+            ),
+            BaseClass::CharacterBody2D | BaseClass::CharacterBody3D | BaseClass::RigidBody2D | BaseClass::RigidBody3D => format!(
+                "    fn ready(&mut self) {{\n        godot_print!(\"{} is ready.\");\n    }}\n\n    fn physics_process(&mut self, _delta: f64) {{}}\n",
+                class_name
+            ),
+            BaseClass::Resource | BaseClass::RefCounted => String::new(),
+            BaseClass::EditorPlugin => format!(
+                "    fn enter_tree(&mut self) {{\n        godot_print!(\"{} enabled.\");\n    }}\n\n    fn exit_tree(&mut self) {{}}\n",
+                class_name
+            ),
+        }
+    }
+}
+
+impl std::fmt::Display for BaseClass {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let name = match self {
+            BaseClass::Node => "Node",
+            BaseClass::Node2D => "Node2D",
+            BaseClass::Node3D => "Node3D",
+            BaseClass::Control => "Control",
+            BaseClass::CharacterBody2D => "CharacterBody2D",
+            BaseClass::CharacterBody3D => "CharacterBody3D",
+            BaseClass::RigidBody2D => "RigidBody2D",
+            BaseClass::RigidBody3D => "RigidBody3D",
+            BaseClass::Area2D => "Area2D",
+            BaseClass::Area3D => "Area3D",
+            BaseClass::Resource => "Resource",
+            BaseClass::RefCounted => "RefCounted",
+            BaseClass::EditorPlugin => "EditorPlugin",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl std::str::FromStr for BaseClass {
+    type Err = String;
+
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        BaseClass::ALL
+            .into_iter()
+            .find(|base_class| base_class.to_string() == name)
+            .ok_or_else(|| format!("Unknown base class '{}'.", name))
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Renderer {
+    #[default]
+    ForwardPlus,
+    Mobile,
+    Compatibility,
+}
+
+impl Renderer {
+    // Feature tag and rendering method names as Godot writes them into project.godot
+    fn feature_tag(self) -> &'static str {
+        match self {
+            Renderer::ForwardPlus => "Forward Plus",
+            Renderer::Mobile => "Mobile",
+            Renderer::Compatibility => "GL Compatibility",
+        }
+    }
+
+    fn rendering_method(self) -> &'static str {
+        match self {
+            Renderer::ForwardPlus => "forward_plus",
+            Renderer::Mobile => "mobile",
+            Renderer::Compatibility => "gl_compatibility",
+        }
+    }
+}
+
+impl std::fmt::Display for Renderer {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Renderer::ForwardPlus => write!(f, "Forward+"),
+            Renderer::Mobile => write!(f, "Mobile"),
+            Renderer::Compatibility => write!(f, "Compatibility"),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum License {
+    #[default]
+    None,
+    Mit,
+    Apache2,
+    Mpl2,
+}
+
+impl License {
+    pub const ALL: [License; 4] = [License::None, License::Mit, License::Apache2, License::Mpl2];
+
+    pub fn spdx_id(self) -> Option<&'static str> {
+        match self {
+            License::None => None,
+            License::Mit => Some("MIT"),
+            License::Apache2 => Some("Apache-2.0"),
+            License::Mpl2 => Some("MPL-2.0"),
+        }
+    }
+}
+
+impl std::fmt::Display for License {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.spdx_id().unwrap_or("None"))
+    }
+}
+
+// What to do when the project directory already exists
+#[derive(Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ExistingProject {
+    #[default]
+    Refuse,
+    // Rewrites changed files according to the per-file policies in the metadata file
+    Update,
+    Overwrite,
+    // Only adds the files that are missing, everything already on disk is kept
+    AddMissing,
+    // Adds the crate and .gdextension to a Godot project started without this tool, existing files are only
+    // touched to append missing .gitignore and .gitattributes lines
+    Retrofit,
+}
+
+// What a new Godot project needs besides the Rust side, a retrofitted project already has its own
+const GODOT_PROJECT_FILES: [&str; 3] = ["project.godot", "main.tscn", "icon.svg"];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum FileStatus {
+    Created,
+    Updated,
+    Unchanged,
+    Skipped,
+}
+
+impl std::fmt::Display for FileStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FileStatus::Created => write!(f, "created"),
+            FileStatus::Updated => write!(f, "updated"),
+            FileStatus::Unchanged => write!(f, "unchanged"),
+            FileStatus::Skipped => write!(f, "skipped"),
+        }
+    }
+}
+
+pub async fn read_templates(path: &str) -> Result<ProjectTemplates, String> {
+    let content = tokio::fs::read_to_string(path)
+        .await
+        .map_err(|err| format!("Failed to read {}: {}", path, err))?;
+    parse_templates(path, &content)
+}
+
+fn parse_templates(path: &str, content: &str) -> Result<ProjectTemplates, String> {
+    let mut templates: ProjectTemplates = serde_yaml::from_str(content).map_err(|err| describe_parse_error(path, content, &err))?;
+    templates.requirements.check_tool_version(path)?;
+    // gitignore may be left empty, without these three there is no extension to build
+    for (field, template) in [
+        ("lib_content", &templates.lib_content),
+        ("gdextension", &templates.gdextension),
+        ("cargo_toml", &templates.cargo_toml),
+    ] {
+        if template.trim().is_empty() {
+            return Err(format!("{}: {} is empty, every project is generated from it.", path, field));
+        }
+    }
+    templates.warnings = scan_templates(content);
+    Ok(templates)
+}
+
+// serde_yaml names the line and column, showing that line and a hint for the usual mistakes makes it fixable from the window
+fn describe_parse_error(path: &str, content: &str, err: &serde_yaml::Error) -> String {
+    let mut message = format!("Failed to parse {}: {}", path, err);
+    let line = err.location().and_then(|location| content.lines().nth(location.line().saturating_sub(1)));
+    if let Some(line) = line {
+        message.push_str(&format!("\n    {}", line.trim_end()));
+    }
+    let err = err.to_string();
+    if line.is_some_and(|line| line.contains('\t')) {
+        message.push_str("\nYAML doesn't allow tabs for indentation, indent with spaces.");
+    } else if err.contains("missing field") {
+        message.push_str("\nEvery template file needs gitignore, lib_content, gdextension and cargo_toml.");
+    } else if err.contains("invalid type") {
+        message.push_str("\nTemplates are block strings: put | after the key and indent the text below it.");
+    }
+    message
+}
+
+// Walks the raw file for the lines serde doesn't keep: top level keys start at column 0, entries of a map like licenses at column 2
+fn scan_templates(content: &str) -> Vec<TemplateWarning> {
+    let mut warnings = Vec::new();
+    let mut key = "";
+    let mut field = String::new();
+    let mut placeholders: Option<&[&str]> = None;
+    let mut is_map = false;
+    for (index, line) in content.lines().enumerate() {
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        if !line.starts_with(char::is_whitespace) {
+            let Some((name, value)) = line.split_once(':') else {
+                continue;
+            };
+            key = name.trim();
+            field = key.to_string();
+            is_map = value.trim().is_empty();
+            placeholders = TEMPLATE_FIELDS.iter().find(|(known, _)| *known == key).map(|(_, placeholders)| *placeholders);
+            if placeholders.is_none() {
+                warnings.push(TemplateWarning {
+                    line: index + 1,
+                    field: field.clone(),
+                    kind: TemplateWarningKind::UnknownField,
+                });
+            }
+            continue;
+        }
+        if is_map && line.starts_with("  ") && !line[2..].starts_with(char::is_whitespace) {
+            if let Some((entry, _)) = line.split_once(':') {
+                field = format!("{}.{}", key, entry.trim());
+            }
+            continue;
+        }
+        let Some(placeholders) = placeholders.filter(|_| key != "requirements") else {
+            continue;
+        };
+        for name in line_placeholders(line) {
+            let kind = if !placeholders.contains(&name) && !BUILTIN_VARIABLES.contains(&name) {
+                TemplateWarningKind::UnknownPlaceholder(name.to_string())
+            } else if name == "project_name" && PROJECT_NAME_CHANGED_FIELDS.contains(&key) {
+                TemplateWarningKind::ProjectNameMeaning
+            } else {
+                continue;
+            };
+            warnings.push(TemplateWarning {
+                line: index + 1,
+                field: field.clone(),
+                kind,
+            });
+        }
+    }
+    warnings
+}
+
+// The {name}s on a line, ${...} and {{...}} belong to the generated file's own syntax and are skipped
+fn line_placeholders(line: &str) -> Vec<&str> {
+    line.match_indices('{')
+        .filter_map(|(open, _)| {
+            let after = &line[open + 1..];
+            let name = &after[..after.find('}')?];
+            let escaped = matches!(line[..open].chars().next_back(), Some('{' | '$')) || after[name.len() + 1..].starts_with('}');
+            if is_valid_variable_name(name) && !escaped {
+                Some(name)
+            } else {
+                None
+            }
+        })
+        .collect()
+}
+
+// The unknown keys and placeholders found while loading, a placeholder the user defined as a variable is fine
+pub fn template_warnings(templates: &ProjectTemplates, variables: &BTreeMap<String, String>) -> Vec<String> {
+    templates
+        .warnings
+        .iter()
+        .filter_map(|warning| match &warning.kind {
+            TemplateWarningKind::UnknownField => Some(format!(
+                "Line {}: {} isn't a template gen_gdext reads and is ignored, check the spelling.",
+                warning.line, warning.field
+            )),
+            TemplateWarningKind::UnknownPlaceholder(name) if variables.contains_key(name) => None,
+            TemplateWarningKind::UnknownPlaceholder(name) => Some(format!(
+                "Line {}: {{{}}} in {} is never filled, fix the name or add it as a template variable.",
+                warning.line, name, warning.field
+            )),
+            TemplateWarningKind::ProjectNameMeaning => Some(format!(
+                "Line {}: {{project_name}} in {} is the project's folder name now, use {{crate_name}} or {{class_prefix}} \
+                 if the template set was written for the crate name or class prefix.",
+                warning.line, warning.field
+            )),
+        })
+        .collect()
+}
+
+// The templates the editor window offers, the rest are edited in templates.yaml itself
+pub const EDITABLE_TEMPLATES: [&str; 4] = ["gitignore", "lib_content", "gdextension", "cargo_toml"];
+
+impl ProjectTemplates {
+    pub fn editable_mut(&mut self, field: &str) -> Option<&mut String> {
+        match field {
+            "gitignore" => Some(&mut self.gitignore),
+            "lib_content" => Some(&mut self.lib_content),
+            "gdextension" => Some(&mut self.gdextension),
+            "cargo_toml" => Some(&mut self.cargo_toml),
+            _ => None,
+        }
+    }
+
+    // A template holding one text by its key, None for the maps, the requirements and the hooks
+    pub fn section(&self, field: &str) -> Option<&str> {
+        Some(match field {
+            "gitignore" => &self.gitignore,
+            "project_gitignore" => &self.project_gitignore,
+            "gitattributes" => &self.gitattributes,
+            "lib_content" => &self.lib_content,
+            "lib_content_example" => &self.lib_content_example,
+            "gdextension" => &self.gdextension,
+            "cargo_toml" => &self.cargo_toml,
+            "cargo_profile_minimal_size" => &self.cargo_profile_minimal_size,
+            "cargo_profile_release" => &self.cargo_profile_release,
+            "cargo_profile_custom" => &self.cargo_profile_custom,
+            "cargo_workspace" => &self.cargo_workspace,
+            "rustfmt_toml" => &self.rustfmt_toml,
+            "clippy_toml" => &self.clippy_toml,
+            "cargo_lints" => &self.cargo_lints,
+            "class_definition" => &self.class_definition,
+            "class_module" => &self.class_module,
+            "prelude_module" => &self.prelude_module,
+            "class_registry" => &self.class_registry,
+            "main_scene" => &self.main_scene,
+            "icon_svg" => &self.icon_svg,
+            "class_icon" => &self.class_icon,
+            "readme" => &self.readme,
+            "readme_class_registry" => &self.readme_class_registry,
+            "copy_artifacts_script" => &self.copy_artifacts_script,
+            "copy_artifacts_script_windows" => &self.copy_artifacts_script_windows,
+            "github_workflow" => &self.github_workflow,
+            "export_plugin_cfg" => &self.export_plugin_cfg,
+            "export_plugin_script" => &self.export_plugin_script,
+            "export_plugin_hook" => &self.export_plugin_hook,
+            "vscode_settings" => &self.vscode_settings,
+            "vscode_tasks" => &self.vscode_tasks,
+            "vscode_launch" => &self.vscode_launch,
+            "justfile" => &self.justfile,
+            "makefile" => &self.makefile,
+            "addon_plugin_cfg" => &self.addon_plugin_cfg,
+            "editor_plugin_cfg" => &self.editor_plugin_cfg,
+            "editor_plugin_script" => &self.editor_plugin_script,
+            "itest_module" => &self.itest_module,
+            "itest_scene" => &self.itest_scene,
+            "itest_script" => &self.itest_script,
+            _ => return None,
+        })
+    }
+
+    // The keys of TEMPLATE_FIELDS whose text the set fills in
+    pub fn filled_sections(&self) -> Vec<&'static str> {
+        TEMPLATE_FIELDS
+            .iter()
+            .map(|(field, _)| *field)
+            .filter(|field| self.section(field).is_some_and(|content| !content.trim().is_empty()))
+            .collect()
+    }
+}
+
+// Writes one template back into the file, comments and the other templates keep their formatting
+pub fn save_template(path: &str, field: &str, template: &str) -> Result<(), String> {
+    let content = fs::read_to_string(path).map_err(|err| format!("Failed to read {}: {}", path, err))?;
+    write_atomic(Path::new(path), &replace_template_block(&content, field, template)).map_err(|err| format!("Failed to write {}: {}", path, err))
+}
+
+// Swaps the block of a top level key for `template` written as a literal block, a missing key is appended
+fn replace_template_block(content: &str, field: &str, template: &str) -> String {
+    let trailing_newlines = template.len() - template.trim_end_matches('\n').len();
+    let chomping = match trailing_newlines {
+        0 => "-",
+        1 => "",
+        _ => "+",
+    };
+    // A first line starting with a space would otherwise be taken for the block's indentation
+    let indentation = if template.starts_with(' ') { "2" } else { "" };
+    let mut block = vec![format!("{}: |{}{}", field, indentation, chomping)];
+    block.extend(template.lines().map(|line| if line.is_empty() { String::new() } else { format!("  {}", line) }));
+
+    let lines: Vec<&str> = content.lines().collect();
+    let is_key = |line: &str| line.strip_prefix(field).is_some_and(|rest| rest.starts_with(':'));
+    let Some(start) = lines.iter().position(|line| is_key(line)) else {
+        let mut content = content.to_string();
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        return format!("{}\n{}\n", content, block.join("\n"));
+    };
+    let mut end = lines[start + 1..]
+        .iter()
+        .position(|line| !line.is_empty() && !line.starts_with(char::is_whitespace))
+        .map_or(lines.len(), |offset| start + 1 + offset);
+    // The blank lines separating it from the next key stay where they are
+    while end > start + 1 && lines[end - 1].trim().is_empty() {
+        end -= 1;
+    }
+
+    let mut replaced: Vec<String> = lines[..start].iter().map(|line| line.to_string()).collect();
+    replaced.extend(block);
+    replaced.extend(lines[end..].iter().map(|line| line.to_string()));
+    let mut replaced = replaced.join("\n");
+    if content.ends_with('\n') {
+        replaced.push('\n');
+    }
+    replaced
+}
+
+// One preset per platform among the selected targets, numbered in the order the platforms first appear
+fn get_export_presets_content(templates: &ProjectTemplates, options: &ProjectOptions, variables: &BTreeMap<String, String>) -> String {
+    let mut platforms: Vec<&str> = Vec::new();
+    for platform in options.targets.iter().filter_map(|target| target.split('.').next()) {
+        if !platforms.contains(&platform) {
+            platforms.push(platform);
+        }
+    }
+    let presets: Vec<String> = platforms
+        .iter()
+        .filter_map(|platform| templates.export_presets.get(*platform))
+        .enumerate()
+        .map(|(index, preset)| render(preset, variables, &[("preset_index", &index.to_string())]))
+        .collect();
+    presets.join("\n")
+}
+
+pub fn get_gitignore_content(templates: &ProjectTemplates, options: &ProjectOptions, variables: &BTreeMap<String, String>) -> String {
+    let mut content = render(&templates.gitignore, variables, &[]);
+    // A target dir inside the crate is ignored from the crate's own .gitignore
+    if options.has_custom_target_dir() && options.target_dir_in_crate() {
+        if !content.is_empty() && !content.ends_with('\n') {
+            content.push('\n');
+        }
+        content.push_str(&format!("/{}/\n", options.target_dir().trim_end_matches('/')));
+    }
+    for (platform, fragment) in &templates.gitignore_fragments {
+        if options.targets.iter().any(|target| target.split('.').next() == Some(platform.as_str())) {
+            for line in render(fragment, variables, &[]).lines() {
+                if !content.lines().any(|existing| existing == line) {
+                    if !content.is_empty() && !content.ends_with('\n') {
+                        content.push('\n');
+                    }
+                    content.push_str(line);
+                    content.push('\n');
+                }
+            }
+        }
+    }
+    content
+}
+
+pub fn get_project_godot_content(names: &ProjectNames, options: &ProjectOptions) -> String {
+    let mut content = format!(
+        "; Engine configuration file.\n\nconfig_version=5\n\n[application]\n\nconfig/name=\"{}\"\nrun/main_scene=\"res://main.tscn\"\nconfig/features=PackedStringArray(\"{}\", \"{}\")\nconfig/icon=\"res://icon.svg\"\n",
+        names.display.replace('"', "\\\""),
+        options.godot_version.feature_tag(),
+        options.renderer.feature_tag()
+    );
+    let mut plugins = Vec::new();
+    if options.editor_plugin {
+        plugins.push(format!("\"res://{}/plugin.cfg\"", EDITOR_PLUGIN_DIR));
+    }
+    if options.library_layout == LibraryLayout::Split {
+        plugins.push(format!("\"res://{}/plugin.cfg\"", EXPORT_PLUGIN_DIR));
+    }
+    if !plugins.is_empty() {
+        content.push_str(&format!("\n[editor_plugins]\n\nenabled=PackedStringArray({})\n", plugins.join(", ")));
+    }
+    if !options.locale.trim().is_empty() {
+        content.push_str(&format!("\n[internationalization]\n\nlocale/fallback=\"{}\"\n", options.locale.trim()));
+    }
+    if options.renderer != Renderer::ForwardPlus {
+        content.push_str(&format!(
+            "\n[rendering]\n\nrenderer/rendering_method=\"{0}\"\nrenderer/rendering_method.mobile=\"{0}\"\n",
+            options.renderer.rendering_method()
+        ));
+    }
+    content
+}
+
+fn require_template<'a>(name: &'static str, template: &'a str) -> Result<&'a str, CreateError> {
+    if template.trim().is_empty() {
+        return Err(CreateError::MissingTemplate(name));
+    }
+    Ok(template)
 }
 
 pub fn convert_to_camel_case(input: &str) -> String {
@@ -26,132 +1693,3490 @@ pub fn convert_to_camel_case(input: &str) -> String {
                 None => String::new(),
             }
         })
-        .collect::<String>()
+        .collect::<String>()
+}
+
+pub fn get_cargo_toml_content(
+    templates: &ProjectTemplates,
+    names: &ProjectNames,
+    minimal_size: bool,
+    release_profile: Option<&ReleaseProfile>,
+    features: &[String],
+    license: License,
+    variables: &BTreeMap<String, String>,
+) -> Result<String, CreateError> {
+    let godot_features = if features.is_empty() {
+        String::new()
+    } else {
+        let quoted: Vec<String> = features.iter().map(|feature| format!("\"{}\"", feature)).collect();
+        format!(", features = [{}]", quoted.join(", "))
+    };
+    let license = license.spdx_id().map(|id| format!("\nlicense = \"{}\"", id)).unwrap_or_default();
+    // Extension crates are rendered with the main crate's variables, {crate_name} is the one being written
+    let mut content = render(
+        &templates.cargo_toml,
+        variables,
+        &[("crate_name", &names.crate_name), ("godot_features", &godot_features), ("license", &license)],
+    );
+    if minimal_size {
+        content.push_str(&render(
+            require_template("cargo_profile_minimal_size", &templates.cargo_profile_minimal_size)?,
+            variables,
+            &[],
+        ));
+    } else if let Some(release_profile) = release_profile.filter(|profile| !profile.is_empty()) {
+        content.push_str(&render(
+            require_template("cargo_profile_release", &templates.cargo_profile_release)?,
+            variables,
+            &[("settings", &release_profile.settings())],
+        ));
+    }
+    Ok(content)
+}
+
+// A [profile.<name>] for each profile targets are mapped to besides cargo's own, one shared by debug and release targets inherits from release
+fn get_custom_profiles(templates: &ProjectTemplates, options: &ProjectOptions, variables: &BTreeMap<String, String>) -> Result<String, CreateError> {
+    let mut profiles: BTreeMap<String, &str> = BTreeMap::new();
+    for target in &options.targets {
+        let (Some(profile), Some(default)) = (options.target_profile(target), default_profile(target)) else {
+            continue;
+        };
+        if is_builtin_profile(&profile) {
+            continue;
+        }
+        let inherits = profiles.entry(profile).or_insert("dev");
+        if default == "release" {
+            *inherits = "release";
+        }
+    }
+
+    let mut content = String::new();
+    for (profile, inherits) in profiles {
+        // Where a distribution profile pays off, the build time goes into a smaller and faster library
+        let settings = if inherits == "release" {
+            ReleaseProfile {
+                lto: Some("fat".to_string()),
+                codegen_units: Some(1),
+                ..Default::default()
+            }
+            .settings()
+        } else {
+            String::new()
+        };
+        let section = render(
+            require_template("cargo_profile_custom", &templates.cargo_profile_custom)?,
+            variables,
+            &[("profile", &profile), ("inherits", inherits), ("settings", &settings)],
+        );
+        content.push_str(section.trim_end());
+        content.push('\n');
+    }
+    Ok(content)
+}
+
+// "serde = { version = \"1.0.210\", features = [\"derive\"] }" for each selected dependency
+fn dependency_lines(options: &ProjectOptions) -> Vec<String> {
+    KNOWN_DEPENDENCIES
+        .iter()
+        .filter(|(name, _, _)| options.dependencies.iter().any(|selected| selected == name))
+        .map(|(name, fallback, features)| {
+            let version = options.dependency_versions.get(*name).map_or(*fallback, String::as_str);
+            if features.is_empty() {
+                format!("{} = \"{}\"", name, version)
+            } else {
+                let quoted: Vec<String> = features.iter().map(|feature| format!("\"{}\"", feature)).collect();
+                format!("{} = {{ version = \"{}\", features = [{}] }}", name, version, quoted.join(", "))
+            }
+        })
+        .collect()
+}
+
+// Inserts the lines after the last entry of the [dependencies] table, skipping crates the template already lists
+fn add_dependencies(manifest: &str, lines: &[String]) -> String {
+    let mut manifest_lines: Vec<&str> = manifest.lines().collect();
+    let Some(table) = manifest_lines.iter().position(|line| line.trim() == "[dependencies]") else {
+        let mut content = manifest.to_string();
+        if !lines.is_empty() {
+            content.push_str(&format!("\n[dependencies]\n{}\n", lines.join("\n")));
+        }
+        return content;
+    };
+    let table_end = manifest_lines[table + 1..]
+        .iter()
+        .position(|line| line.trim_start().starts_with('['))
+        .map_or(manifest_lines.len(), |offset| table + 1 + offset);
+    let listed: Vec<&str> = manifest_lines[table + 1..table_end]
+        .iter()
+        .filter_map(|line| line.split_once('=').map(|(key, _)| key.trim()))
+        .collect();
+    let mut insert_at = table_end;
+    while insert_at > table + 1 && manifest_lines[insert_at - 1].trim().is_empty() {
+        insert_at -= 1;
+    }
+    let added: Vec<&str> = lines
+        .iter()
+        .map(String::as_str)
+        .filter(|line| line.split_once('=').is_some_and(|(key, _)| !listed.contains(&key.trim())))
+        .collect();
+    manifest_lines.splice(insert_at..insert_at, added);
+    let mut content = manifest_lines.join("\n");
+    if manifest.ends_with('\n') {
+        content.push('\n');
+    }
+    content
+}
+
+// The newest version of the crate through cargo search, None offline or when cargo can't be run
+fn latest_crate_version(options: &ProjectOptions, crate_name: &str) -> Option<String> {
+    let output = Command::new(options.cargo_program())
+        .envs(&options.env)
+        .args(["search", "--limit", "1", crate_name])
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())?;
+    // The search is fuzzy, only a line for exactly this crate counts: serde = "1.0.210"    # A generic serialization...
+    let prefix = format!("{} = \"", crate_name);
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    let line = stdout.lines().find(|line| line.starts_with(&prefix))?;
+    line[prefix.len()..].split('"').next().map(str::to_string)
+}
+
+fn resolve_dependency_versions(options: &ProjectOptions, log: &Arc<Mutex<Log>>) -> BTreeMap<String, String> {
+    let mut versions = BTreeMap::new();
+    for (name, fallback, _) in KNOWN_DEPENDENCIES
+        .iter()
+        .filter(|(name, _, _)| options.dependencies.iter().any(|selected| selected == name))
+    {
+        match latest_crate_version(options, name) {
+            Some(version) => {
+                log.lock().unwrap().info(&format!("Using {} {}.", name, version));
+                versions.insert(name.to_string(), version);
+            }
+            None => log
+                .lock()
+                .unwrap()
+                .warn(&format!("Couldn't look up the newest {} on crates.io, using version \"{}\".", name, fallback)),
+        }
+    }
+    versions
+}
+
+// The project name is the directory the project is created in, so anything a folder name can hold except surrounding spaces
+pub fn is_valid_project_name(project_name: &str) -> bool {
+    project_name.trim() == project_name
+        && !matches!(project_name, "." | "..")
+        && !project_name
+            .chars()
+            .any(|c| c.is_control() || matches!(c, '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|'))
+}
+
+// Crates every Rust program can refer to, cargo refuses packages named after them
+const STD_CRATES: [&str; 5] = ["std", "core", "alloc", "proc_macro", "test"];
+
+// Why cargo would refuse the crate name, checked against the library name since that's the identifier the code sees
+fn crate_name_problem(crate_name: &str) -> Option<&'static str> {
+    let library_name = crate_name.replace('-', "_");
+    if crate_name.is_empty() {
+        Some("the project name has no letters or digits to derive one from, enter a crate name")
+    } else if !crate_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-') {
+        Some("use letters, digits, '_' and '-'")
+    } else if crate_name.starts_with(|c: char| c.is_ascii_digit()) {
+        Some("it can't start with a digit")
+    } else if RUST_KEYWORDS.contains(&library_name.as_str()) {
+        Some("it is a Rust keyword")
+    } else if STD_CRATES.contains(&library_name.as_str()) {
+        Some("it is the name of a standard library crate")
+    } else {
+        None
+    }
+}
+
+// Exported as a C symbol and written into the macro attribute, so an identifier either way
+fn is_valid_entry_symbol(entry_symbol: &str) -> bool {
+    entry_symbol.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') && entry_symbol.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// The prefix names the ExtensionLibrary struct and the main class, so it has to be a Rust identifier
+fn is_valid_class_prefix(class_prefix: &str) -> bool {
+    class_prefix.starts_with(|c: char| c.is_ascii_alphabetic()) && class_prefix.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// The names a project goes by: the Godot project name, the cargo package and the prefix of the generated classes
+#[derive(Clone)]
+pub struct ProjectNames {
+    pub display: String,
+    pub crate_name: String,
+    pub class_prefix: String,
+}
+
+impl ProjectNames {
+    // Whatever the options leave empty is derived, "My Cool Game" becomes my_cool_game and MyCoolGame
+    pub fn new(project_name: &str, options: &ProjectOptions) -> Self {
+        let display = match options.display_name.trim() {
+            "" => project_name.trim(),
+            display_name => display_name,
+        };
+        let crate_name = match options.crate_name.trim() {
+            "" => derive_crate_name(project_name),
+            crate_name => crate_name.to_string(),
+        };
+        Self::with_crate_name(display, crate_name, options.class_prefix.trim())
+    }
+
+    // Existing projects go by the package name in their Cargo.toml, older ones named the crate after the project
+    pub fn load(project_name: &str) -> Self {
+        let manifest = find_project_dir(project_name).join(find_crate_dir(project_name)).join("Cargo.toml");
+        let crate_name = fs::read_to_string(manifest)
+            .ok()
+            .and_then(|manifest| package_name(&manifest))
+            .unwrap_or_else(|| derive_crate_name(project_name));
+        Self::with_crate_name(project_name, crate_name, "")
+    }
+
+    // Extension crates in the main crate's workspace go by their folder name
+    pub fn for_extension_crate(crate_name: &str) -> Self {
+        Self::with_crate_name(crate_name, crate_name.to_string(), "")
+    }
+
+    fn with_crate_name(display: &str, crate_name: String, class_prefix: &str) -> Self {
+        let class_prefix = match class_prefix {
+            "" => convert_to_camel_case(&crate_name.replace('-', "_")),
+            class_prefix => class_prefix.to_string(),
+        };
+        Self {
+            display: display.to_string(),
+            crate_name,
+            class_prefix,
+        }
+    }
+
+    // cargo names the library files after the crate with '-' turned into '_'
+    pub fn library_name(&self) -> String {
+        self.crate_name.replace('-', "_")
+    }
+}
+
+// The main crate first, then every extension crate of its workspace
+pub fn workspace_crates(names: &ProjectNames, extension_crates: &[String]) -> Vec<ProjectNames> {
+    std::iter::once(names.clone())
+        .chain(extension_crates.iter().map(|crate_name| ProjectNames::for_extension_crate(crate_name)))
+        .collect()
+}
+
+// "My Cool Game" -> "my_cool_game", "MyGame" -> "my_game", a leading digit gets a prefix since cargo doesn't allow one
+pub fn derive_crate_name(project_name: &str) -> String {
+    let words: Vec<String> = project_name
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(convert_to_snake_case)
+        .collect();
+    let crate_name = words.join("_");
+    if crate_name.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("gd_{}", crate_name)
+    } else {
+        crate_name
+    }
+}
+
+// The name in the [package] table, enough of TOML for the manifests this tool writes
+fn package_name(manifest: &str) -> Option<String> {
+    let mut in_package = false;
+    for line in manifest.lines().map(str::trim) {
+        if line.starts_with('[') {
+            in_package = line == "[package]";
+        } else if let Some((key, value)) = line.split_once('=').filter(|_| in_package) {
+            if key.trim() == "name" {
+                return Some(value.trim().trim_matches('"').to_string());
+            }
+        }
+    }
+    None
+}
+
+// The Rust class the generated main.tscn instantiates as its root node
+pub fn main_class_name(names: &ProjectNames) -> String {
+    format!("{}Main", names.class_prefix)
+}
+
+// The main class and every additional one with its icon's path and content, colored like the editor's icons for the base class
+fn get_class_icons(
+    templates: &ProjectTemplates,
+    names: &ProjectNames,
+    project_name: &str,
+    options: &ProjectOptions,
+    variables: &BTreeMap<String, String>,
+) -> Result<Vec<(String, String, String)>, CreateError> {
+    let template = require_template("class_icon", &templates.class_icon)?;
+    let classes =
+        std::iter::once((main_class_name(names), options.main_base_class())).chain(options.classes.iter().map(|class| (class.name.clone(), class.base_class)));
+    Ok(classes
+        .map(|(class_name, base_class)| {
+            // "DemoMain" gets an M, the prefix is the same for every class of the project
+            let initial = class_name
+                .strip_prefix(names.class_prefix.as_str())
+                .filter(|rest| !rest.is_empty())
+                .unwrap_or(&class_name)
+                .chars()
+                .next()
+                .unwrap_or('?');
+            let content = render(
+                template,
+                variables,
+                &[("icon_color", base_class.icon_color()), ("icon_initial", &glyph_path(initial))],
+            );
+            (class_name.clone(), format!("addons/{}/icons/{}.svg", project_name, class_name), content)
+        })
+        .collect())
+}
+
+// Godot's SVG importer doesn't render <text>, letters and digits are drawn from a 3x5 pixel font instead, one row per byte
+const GLYPHS: [(char, [u8; 5]); 36] = [
+    ('A', [0b010, 0b101, 0b111, 0b101, 0b101]),
+    ('B', [0b110, 0b101, 0b110, 0b101, 0b110]),
+    ('C', [0b011, 0b100, 0b100, 0b100, 0b011]),
+    ('D', [0b110, 0b101, 0b101, 0b101, 0b110]),
+    ('E', [0b111, 0b100, 0b110, 0b100, 0b111]),
+    ('F', [0b111, 0b100, 0b110, 0b100, 0b100]),
+    ('G', [0b011, 0b100, 0b101, 0b101, 0b011]),
+    ('H', [0b101, 0b101, 0b111, 0b101, 0b101]),
+    ('I', [0b111, 0b010, 0b010, 0b010, 0b111]),
+    ('J', [0b001, 0b001, 0b001, 0b101, 0b010]),
+    ('K', [0b101, 0b101, 0b110, 0b101, 0b101]),
+    ('L', [0b100, 0b100, 0b100, 0b100, 0b111]),
+    ('M', [0b101, 0b111, 0b111, 0b101, 0b101]),
+    ('N', [0b110, 0b101, 0b101, 0b101, 0b101]),
+    ('O', [0b010, 0b101, 0b101, 0b101, 0b010]),
+    ('P', [0b110, 0b101, 0b110, 0b100, 0b100]),
+    ('Q', [0b010, 0b101, 0b101, 0b110, 0b011]),
+    ('R', [0b110, 0b101, 0b110, 0b101, 0b101]),
+    ('S', [0b011, 0b100, 0b010, 0b001, 0b110]),
+    ('T', [0b111, 0b010, 0b010, 0b010, 0b010]),
+    ('U', [0b101, 0b101, 0b101, 0b101, 0b111]),
+    ('V', [0b101, 0b101, 0b101, 0b101, 0b010]),
+    ('W', [0b101, 0b101, 0b111, 0b111, 0b101]),
+    ('X', [0b101, 0b101, 0b010, 0b101, 0b101]),
+    ('Y', [0b101, 0b101, 0b010, 0b010, 0b010]),
+    ('Z', [0b111, 0b001, 0b010, 0b100, 0b111]),
+    ('0', [0b111, 0b101, 0b101, 0b101, 0b111]),
+    ('1', [0b010, 0b110, 0b010, 0b010, 0b111]),
+    ('2', [0b110, 0b001, 0b010, 0b100, 0b111]),
+    ('3', [0b110, 0b001, 0b010, 0b001, 0b110]),
+    ('4', [0b101, 0b101, 0b111, 0b001, 0b001]),
+    ('5', [0b111, 0b100, 0b110, 0b001, 0b110]),
+    ('6', [0b011, 0b100, 0b111, 0b101, 0b111]),
+    ('7', [0b111, 0b001, 0b010, 0b010, 0b010]),
+    ('8', [0b111, 0b101, 0b111, 0b101, 0b111]),
+    ('9', [0b111, 0b101, 0b111, 0b001, 0b110]),
+];
+
+// SVG path data drawing the glyph with 2x2 squares, centered in a 16x16 icon
+fn glyph_path(c: char) -> String {
+    let c = c.to_ascii_uppercase();
+    let rows = GLYPHS
+        .iter()
+        .find(|(glyph, _)| *glyph == c)
+        .map_or([0b111, 0b101, 0b101, 0b101, 0b111], |(_, rows)| *rows);
+    let mut path = String::new();
+    for (row, bits) in rows.iter().enumerate() {
+        for column in 0..3 {
+            if bits & (0b100 >> column) != 0 {
+                path.push_str(&format!("M{} {}h2v2h-2z", 5 + column * 2, 3 + row * 2));
+            }
+        }
+    }
+    path
+}
+
+// "PlayerController" -> "player_controller", "HTTPClient" -> "http_client"
+pub fn convert_to_snake_case(input: &str) -> String {
+    let chars: Vec<char> = input.chars().collect();
+    let mut output = String::new();
+    for (index, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() && index > 0 {
+            let previous = chars[index - 1];
+            let next_is_lowercase = chars.get(index + 1).is_some_and(|next| next.is_lowercase());
+            if previous.is_lowercase() || previous.is_ascii_digit() || (previous.is_uppercase() && next_is_lowercase) {
+                output.push('_');
+            }
+        }
+        output.extend(c.to_lowercase());
+    }
+    output
+}
+
+// Keywords can't be module names
+const RUST_KEYWORDS: &[&str] = &[
+    "as", "async", "await", "box", "break", "const", "continue", "crate", "dyn", "else", "enum", "extern", "false", "fn", "for", "if", "impl", "in", "let",
+    "loop", "match", "mod", "move", "mut", "pub", "ref", "return", "self", "static", "struct", "super", "trait", "true", "type", "unsafe", "use", "where",
+    "while", "yield",
+];
+
+// Returns the module name the class is generated into
+fn class_module_name(class_name: &str) -> Result<String, CreateError> {
+    let module = convert_to_snake_case(class_name);
+    let is_identifier = class_name.starts_with(|c: char| c.is_ascii_alphabetic()) && class_name.chars().all(|c| c.is_ascii_alphanumeric() || c == '_');
+    if !is_identifier || RUST_KEYWORDS.contains(&module.as_str()) {
+        return Err(CreateError::InvalidClassName(class_name.to_string()));
+    }
+    Ok(module)
+}
+
+// "Player:CharacterBody2D, Enemy:Area2D" or one class per line, the base class defaults to Node
+pub fn parse_class_list(input: &str) -> Result<Vec<ClassSpec>, String> {
+    input
+        .split([',', '\n'])
+        .map(str::trim)
+        .filter(|entry| !entry.is_empty())
+        .map(|entry| {
+            let (name, base_class) = entry.split_once(':').unwrap_or((entry, "Node"));
+            Ok(ClassSpec {
+                name: name.trim().to_string(),
+                base_class: base_class.trim().parse()?,
+            })
+        })
+        .collect()
+}
+
+// The placeholders describing a class, `values` come on top
+fn render_class_template(template: &str, variables: &BTreeMap<String, String>, class_name: &str, base_class: BaseClass, values: &[(&str, &str)]) -> String {
+    let class_attributes = base_class.class_attributes();
+    let base_class_name = base_class.to_string();
+    let base_methods = base_class.methods(class_name);
+    let mut class_values = vec![
+        ("class_attributes", class_attributes.as_str()),
+        ("base_class", base_class_name.as_str()),
+        ("base_methods", base_methods.as_str()),
+        ("class_name", class_name),
+    ];
+    class_values.extend_from_slice(values);
+    render(template, variables, &class_values)
+}
+
+pub fn get_lib_content(
+    templates: &ProjectTemplates,
+    names: &ProjectNames,
+    options: &ProjectOptions,
+    variables: &BTreeMap<String, String>,
+) -> Result<String, CreateError> {
+    let mut modules = String::new();
+    if options.prelude_module {
+        modules.push_str("mod prelude;\n");
+    }
+    if options.classes.iter().any(|class| options.layout.module_dir(class.base_class).is_some()) {
+        modules.push_str("mod registry;\n");
+    }
+    // Release builds leave the test node out of the shipped library
+    if options.itest {
+        modules.push_str("#[cfg(debug_assertions)]\nmod itest;\n");
+    }
+    if !modules.is_empty() {
+        modules.push('\n');
+    }
+
+    let mut inline_classes = String::new();
+    if options.layout == CrateLayout::Flat {
+        for class in &options.classes {
+            let definition = render_class_template(
+                require_template("class_definition", &templates.class_definition)?,
+                variables,
+                &class.name,
+                class.base_class,
+                &[],
+            );
+            inline_classes.push_str(&format!("\n\n{}", definition.trim_end()));
+        }
+    }
+
+    // The .gdextension only finds a renamed entry point if the macro exports it under that name
+    let extension_attribute = match options.entry_symbol() {
+        DEFAULT_ENTRY_SYMBOL => "#[gdextension]".to_string(),
+        entry_symbol => format!("#[gdextension(entry_symbol = {})]", entry_symbol),
+    };
+
+    let lib_content = match options.lib_scaffold {
+        LibScaffold::Minimal => &templates.lib_content,
+        LibScaffold::Example => require_template("lib_content_example", &templates.lib_content_example)?,
+    };
+
+    // Extension crates are rendered with the main crate's variables, {class_prefix} is the one of the crate being written
+    Ok(render_class_template(
+        lib_content,
+        variables,
+        &main_class_name(names),
+        options.main_base_class(),
+        &[
+            ("modules", &modules),
+            ("inline_classes", &inline_classes),
+            ("extension_attribute", &extension_attribute),
+            ("class_prefix", &names.class_prefix),
+        ],
+    ))
+}
+
+// The class definition is part of the template rather than a value, its class placeholders are filled in the same pass
+pub fn get_class_module_content(
+    templates: &ProjectTemplates,
+    class: &ClassSpec,
+    use_prelude: bool,
+    variables: &BTreeMap<String, String>,
+) -> Result<String, CreateError> {
+    let module = require_template("class_module", &templates.class_module)?.replace(
+        "{class_definition}",
+        require_template("class_definition", &templates.class_definition)?.trim_end(),
+    );
+    let prelude = if use_prelude { "crate::prelude" } else { "godot::prelude" };
+    Ok(render_class_template(
+        &module,
+        variables,
+        &class.name,
+        class.base_class,
+        &[("prelude", prelude)],
+    ))
+}
+
+fn class_registry_entry(dir: &str, module: &str) -> String {
+    format!("class!(\"{}/{}.rs\", {});\n", dir, module, module)
+}
+
+fn check_classes(names: &ProjectNames, classes: &[ClassSpec]) -> Result<(), CreateError> {
+    // Compared by module name, two classes can't share a file
+    let mut modules = vec![convert_to_snake_case(&main_class_name(names))];
+    for class in classes {
+        let module = class_module_name(&class.name)?;
+        if modules.contains(&module) {
+            return Err(CreateError::DuplicateClass(class.name.clone()));
+        }
+        modules.push(module);
+    }
+    Ok(())
+}
+
+// Filled into every generated file, user-defined variables can't shadow these
+pub const BUILTIN_VARIABLES: [&str; 9] = [
+    "project_name",
+    "project_name_camel",
+    "project_name_snake",
+    "display_name",
+    "crate_name",
+    "class_prefix",
+    "godot_version",
+    "year",
+    "author",
+];
+
+fn template_variables(project_name: &str, names: &ProjectNames, options: &ProjectOptions) -> BTreeMap<String, String> {
+    let snake = derive_crate_name(project_name);
+    let builtins = [
+        project_name.to_string(),
+        convert_to_camel_case(&snake),
+        snake,
+        names.display.clone(),
+        names.crate_name.clone(),
+        names.class_prefix.clone(),
+        options.godot_version.to_string(),
+        current_year().to_string(),
+        options.author.trim().to_string(),
+    ];
+    let mut variables = options.template_variables.clone();
+    variables.extend(BUILTIN_VARIABLES.iter().map(|name| name.to_string()).zip(builtins));
+    variables
+}
+
+// Replaces each {name} of a known variable in a single pass, so values are never filled again and other braces stay as they are
+pub fn fill_variables(template: &str, variables: &BTreeMap<String, String>) -> String {
+    let mut filled = String::with_capacity(template.len());
+    let mut rest = template;
+    while let Some(open) = rest.find('{') {
+        filled.push_str(&rest[..open]);
+        let after = &rest[open + 1..];
+        let variable = after.find('}').and_then(|close| variables.get(&after[..close]).map(|value| (close, value)));
+        match variable {
+            Some((close, value)) => {
+                filled.push_str(value);
+                rest = &after[close + 1..];
+            }
+            None => {
+                filled.push('{');
+                rest = after;
+            }
+        }
+    }
+    filled.push_str(rest);
+    filled
+}
+
+// One fill_variables pass with the values a template gets from the tool on top of the variables, so nothing filled is filled again
+fn render(template: &str, variables: &BTreeMap<String, String>, values: &[(&str, &str)]) -> String {
+    let mut variables = variables.clone();
+    variables.extend(values.iter().map(|(name, value)| (name.to_string(), value.to_string())));
+    fill_variables(template, &variables)
+}
+
+// Variable names follow the built-in ones: lowercase letters, digits and '_'
+fn is_valid_variable_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase() || c == '_') && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_')
+}
+
+// The current year from the system clock, enough for a copyright line without pulling in a date crate
+fn current_year() -> u64 {
+    let mut days = SystemTime::now()
+        .duration_since(SystemTime::UNIX_EPOCH)
+        .map_or(0, |elapsed| elapsed.as_secs() / 86_400);
+    let mut year = 1970;
+    loop {
+        let year_days = if year % 4 == 0 && (year % 100 != 0 || year % 400 == 0) { 366 } else { 365 };
+        if days < year_days {
+            return year;
+        }
+        days -= year_days;
+        year += 1;
+    }
+}
+
+pub fn get_license_content(templates: &ProjectTemplates, license: License, variables: &BTreeMap<String, String>) -> Result<Option<String>, CreateError> {
+    let Some(id) = license.spdx_id() else {
+        return Ok(None);
+    };
+    let text = templates
+        .licenses
+        .get(id)
+        .filter(|text| !text.trim().is_empty())
+        .ok_or(CreateError::MissingTemplate(id))?;
+    Ok(Some(render(text, variables, &[])))
+}
+
+pub fn get_main_scene_content(
+    templates: &ProjectTemplates,
+    names: &ProjectNames,
+    base_class: BaseClass,
+    variables: &BTreeMap<String, String>,
+) -> Result<String, CreateError> {
+    let root_type = if base_class.can_be_scene_root() {
+        main_class_name(names)
+    } else {
+        "Node".to_string()
+    };
+    Ok(render(
+        require_template("main_scene", &templates.main_scene)?,
+        variables,
+        &[("scene_root_type", &root_type)],
+    ))
+}
+
+// The file name a target's library is built as
+fn library_file(names: &ProjectNames, target: &str) -> Option<String> {
+    let library_name = names.library_name();
+    match target {
+        "linux.debug.x86_64" | "linux.release.x86_64" => Some(format!("lib{}.so", library_name)),
+        "windows.debug.x86_64" | "windows.release.x86_64" => Some(format!("{}.dll", library_name)),
+        "macos.debug" | "macos.release" => Some(format!("lib{}.dylib", library_name)),
+        _ => None,
+    }
+}
+
+// The profile a target builds with unless it's mapped to another one, after the debug or release in its name
+pub fn default_profile(target: &str) -> Option<&'static str> {
+    match target.split('.').nth(1)? {
+        "debug" => Some("debug"),
+        "release" => Some("release"),
+        _ => None,
+    }
+}
+
+// Cargo's own profiles, every other one a target is mapped to gets a [profile.<name>] in Cargo.toml
+fn is_builtin_profile(profile: &str) -> bool {
+    matches!(profile, "debug" | "release" | "test" | "bench")
+}
+
+fn is_valid_profile_name(profile: &str) -> bool {
+    profile.starts_with(|c: char| c.is_ascii_alphabetic()) && profile.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+// What selects the profile on cargo's command line, "debug" being the dev profile cargo builds without a flag
+fn profile_args(profile: &str) -> Vec<String> {
+    match profile {
+        "debug" => Vec::new(),
+        "release" => vec!["--release".to_string()],
+        profile => vec!["--profile".to_string(), profile.to_string()],
+    }
+}
+
+// "linux.debug.x86_64" -> "linux.debug.editor.x86_64", the tags of the library the editor loads
+fn editor_feature_tags(target: &str) -> String {
+    let mut tags: Vec<&str> = target.split('.').collect();
+    tags.insert(tags.len().min(2), "editor");
+    tags.join(".")
+}
+
+// Where the copy step puts a target's library, e.g. bin/linux/libdemo.linux.debug.x86_64.so
+fn copied_library_path(names: &ProjectNames, target: &str) -> Option<String> {
+    let file = library_file(names, target)?;
+    let platform = target.split('.').next()?;
+    let (stem, extension) = file.rsplit_once('.')?;
+    Some(format!("bin/{}/{}.{}.{}", platform, stem, target, extension))
+}
+
+// Where cargo writes the crate's build output, from the project directory unless the target dir is absolute
+pub fn target_dir_path(crate_dir: &str, target_dir: &str) -> String {
+    if Path::new(target_dir).is_absolute() {
+        return target_dir.to_string();
+    }
+    let path = format!("{}/{}", crate_dir, target_dir);
+    normalize_relative_path(&path).filter(|normalized| !normalized.is_empty()).unwrap_or(path)
+}
+
+// "rust/../build" -> "build", None for absolute paths and ones leaving the project directory
+fn normalize_relative_path(path: &str) -> Option<String> {
+    let mut components = Vec::new();
+    for component in Path::new(path).components() {
+        match component {
+            Component::Normal(name) => components.push(name.to_string_lossy().into_owned()),
+            Component::ParentDir => {
+                components.pop()?;
+            }
+            Component::CurDir => {}
+            Component::RootDir | Component::Prefix(_) => return None,
+        }
+    }
+    Some(components.join("/"))
+}
+
+// A res:// path for anything under the project directory, absolute paths stay as they are
+fn resource_path(path: &str) -> String {
+    if Path::new(path).is_absolute() {
+        path.to_string()
+    } else {
+        format!("res://{}", path)
+    }
+}
+
+// The library cargo builds for a target, from the project directory like target_dir_path
+fn built_library_path(names: &ProjectNames, crate_dir: &str, options: &ProjectOptions, target: &str) -> Option<String> {
+    let file = library_file(names, target)?;
+    let profile = options.target_profile(target)?;
+    Some(format!("{}/{}/{}", target_dir_path(crate_dir, options.target_dir()), profile, file))
+}
+
+// Where the .gdextension loads a target's library from unless it's overridden, the exported one for the split layout
+pub fn default_library_path(names: &ProjectNames, crate_dir: &str, options: &ProjectOptions, target: &str) -> Option<String> {
+    Some(match options.library_layout {
+        LibraryLayout::TargetDir => resource_path(&built_library_path(names, crate_dir, options, target)?),
+        LibraryLayout::Bin | LibraryLayout::Split => format!("res://{}", copied_library_path(names, target)?),
+    })
+}
+
+// The template's entries are kept, the keys the options control are set in it whatever the template wrote for them
+pub fn get_gdextension_content(
+    templates: &ProjectTemplates,
+    names: &ProjectNames,
+    crate_dir: &str,
+    options: &ProjectOptions,
+    icons: &[(String, String)],
+    variables: &BTreeMap<String, String>,
+) -> Result<String, CreateError> {
+    let mut content = String::new();
+    if options.double_precision {
+        content.push_str("; Built with double-precision, only loads in editors and export templates compiled with precision=double\n");
+    }
+    content.push_str(&render(
+        require_template("gdextension", &templates.gdextension)?,
+        variables,
+        &[("crate_name", &names.crate_name)],
+    ));
+    let mut gdextension = GdExtensionFile::parse(&content).map_err(CreateError::InvalidGdExtensionTemplate)?;
+
+    gdextension.set("configuration", "entry_symbol", quote(options.entry_symbol()));
+    gdextension.set("configuration", "compatibility_minimum", options.godot_version.to_string());
+    if let Some(maximum) = options.compatibility_maximum {
+        gdextension.set("configuration", "compatibility_maximum", maximum.to_string());
+    }
+    gdextension.set("configuration", "reloadable", options.reloadable.to_string());
+
+    for target in &options.targets {
+        let path = match options.library_paths.get(target).map(|path| path.trim()) {
+            Some(path) if !path.is_empty() => path.to_string(),
+            _ => match default_library_path(names, crate_dir, options, target) {
+                Some(path) => path,
+                None => continue,
+            },
+        };
+        // Godot picks the entry matching the most feature tags, so the editor one wins wherever "editor" is set
+        if options.library_layout == LibraryLayout::Split {
+            if let Some(built) = built_library_path(names, crate_dir, options, target) {
+                gdextension.set("libraries", &editor_feature_tags(target), quote(&resource_path(&built)));
+            }
+        }
+        gdextension.set("libraries", target, quote(&path));
+    }
+
+    for (class_name, path) in icons {
+        gdextension.set("icons", class_name, quote(path));
+    }
+    Ok(gdextension.to_string())
+}
+
+// Leaves identical files alone so their mtimes stay stable and Godot doesn't reimport them
+pub fn write_if_changed(path: &Path, content: &str) -> std::io::Result<FileStatus> {
+    let status = match fs::read(path) {
+        Ok(existing) if existing == content.as_bytes() => return Ok(FileStatus::Unchanged),
+        Ok(_) => FileStatus::Updated,
+        Err(_) => FileStatus::Created,
+    };
+    write_atomic(path, content)?;
+    Ok(status)
+}
+
+// Appends the rendered lines the file doesn't contain yet, meant for line lists like .gitignore
+fn merge_lines(existing: &str, content: &str) -> String {
+    let mut merged = existing.to_string();
+    for line in content
+        .lines()
+        .filter(|line| !line.trim().is_empty() && !existing.lines().any(|existing_line| existing_line == *line))
+    {
+        if !merged.is_empty() && !merged.ends_with('\n') {
+            merged.push('\n');
+        }
+        merged.push_str(line);
+        merged.push('\n');
+    }
+    merged
+}
+
+enum Change {
+    CreatedDir(PathBuf),
+    CreatedFile(PathBuf),
+    UpdatedFile(PathBuf, String),
+}
+
+pub struct ProjectWriter<'a> {
+    root: PathBuf,
+    log: &'a Arc<Mutex<Log>>,
+    metadata: ProjectMetadata,
+    existing_project: ExistingProject,
+    confirm_overwrite: &'a dyn Fn(&str) -> bool,
+    changes: RefCell<Vec<Change>>,
+}
+
+impl<'a> ProjectWriter<'a> {
+    pub fn new(
+        root: &Path,
+        log: &'a Arc<Mutex<Log>>,
+        existing_project: ExistingProject,
+        confirm_overwrite: &'a dyn Fn(&str) -> bool,
+    ) -> Result<Self, CreateError> {
+        let metadata = ProjectMetadata::load(root)?;
+        Ok(Self {
+            root: root.to_path_buf(),
+            log,
+            metadata,
+            existing_project,
+            confirm_overwrite,
+            changes: RefCell::new(Vec::new()),
+        })
+    }
+
+    // An empty path creates the project root itself
+    pub fn create_dir(&self, relative_path: &str) -> Result<(), CreateError> {
+        let path = self.root.join(relative_path);
+
+        let mut missing = Vec::new();
+        let mut current = path.as_path();
+        while !current.as_os_str().is_empty() && !current.exists() {
+            missing.push(current.to_path_buf());
+            current = current.parent().unwrap_or(Path::new(""));
+        }
+
+        fs::create_dir_all(&path).map_err(|source| CreateError::CreateDir { path, source })?;
+        self.changes.borrow_mut().extend(missing.into_iter().rev().map(Change::CreatedDir));
+        Ok(())
+    }
+
+    pub fn write(&self, relative_path: &str, content: &str) -> Result<FileStatus, CreateError> {
+        let path = self.root.join(relative_path);
+        let existing = fs::read_to_string(&path).ok();
+        let written = match &existing {
+            None => write_if_changed(&path, content),
+            Some(existing) if existing == content => Ok(FileStatus::Unchanged),
+            Some(existing) => match self.policy(relative_path) {
+                FilePolicy::Overwrite => write_if_changed(&path, content),
+                FilePolicy::Never => Ok(FileStatus::Skipped),
+                FilePolicy::Prompt if (self.confirm_overwrite)(relative_path) => write_if_changed(&path, content),
+                FilePolicy::Prompt => Ok(FileStatus::Skipped),
+                FilePolicy::Merge => write_if_changed(&path, &merge_lines(existing, content)),
+            },
+        };
+        let status = written.map_err(|source| CreateError::WriteFile { path: path.clone(), source })?;
+
+        match (status, existing) {
+            (FileStatus::Created, _) => self.changes.borrow_mut().push(Change::CreatedFile(path.clone())),
+            (FileStatus::Updated, Some(existing)) => self.changes.borrow_mut().push(Change::UpdatedFile(path.clone(), existing)),
+            _ => {}
+        }
+
+        let mut log_inner = self.log.lock().unwrap();
+        log_inner.info(&format!("  {:<9} {}", status, path.display()));
+        Ok(status)
+    }
+
+    fn policy(&self, relative_path: &str) -> FilePolicy {
+        match self.existing_project {
+            ExistingProject::Overwrite => FilePolicy::Overwrite,
+            ExistingProject::AddMissing => FilePolicy::Never,
+            ExistingProject::Retrofit if relative_path.ends_with(".gitignore") || relative_path.ends_with(".gitattributes") => FilePolicy::Merge,
+            ExistingProject::Retrofit => FilePolicy::Never,
+            ExistingProject::Refuse | ExistingProject::Update => self.metadata.policy(relative_path),
+        }
+    }
+
+    // For files outside the project directory, e.g. the manifest of an enclosing Cargo workspace
+    pub fn update_file(&self, path: &Path, content: &str) -> Result<(), CreateError> {
+        let write_error = |source| CreateError::WriteFile {
+            path: path.to_path_buf(),
+            source,
+        };
+        let original = fs::read_to_string(path).map_err(write_error)?;
+        let status = write_if_changed(path, content).map_err(write_error)?;
+        if status == FileStatus::Updated {
+            self.changes.borrow_mut().push(Change::UpdatedFile(path.to_path_buf(), original));
+        }
+
+        let mut log_inner = self.log.lock().unwrap();
+        log_inner.info(&format!("  {:<9} {}", status, path.display()));
+        Ok(())
+    }
+
+    // Undoes everything written so far in reverse order; a directory this run created only holds files from this run
+    pub fn rollback(&self) {
+        let changes = self.changes.take();
+        let count = changes.len();
+        for change in changes.into_iter().rev() {
+            let _ = match change {
+                Change::CreatedDir(path) => fs::remove_dir_all(path),
+                Change::CreatedFile(path) => fs::remove_file(path),
+                Change::UpdatedFile(path, original) => write_atomic(&path, &original),
+            };
+        }
+
+        let mut log_inner = self.log.lock().unwrap();
+        log_inner.info(&format!("Rolled back {} changes.", count));
+    }
+}
+
+pub fn library_file_names(names: &ProjectNames) -> [String; 3] {
+    let library_name = names.library_name();
+    [
+        format!("lib{}.so", library_name),
+        format!("{}.dll", library_name),
+        format!("lib{}.dylib", library_name),
+    ]
+}
+
+pub fn write_checksums(profile_dir: &Path, crates: &[ProjectNames]) -> std::io::Result<Vec<PathBuf>> {
+    let mut libraries = Vec::new();
+    let mut manifest = String::new();
+
+    for file_name in crates.iter().flat_map(library_file_names) {
+        let path = profile_dir.join(&file_name);
+        if let Ok(bytes) = fs::read(&path) {
+            // Same layout as sha256sum so `sha256sum -c checksums.txt` works downstream
+            manifest.push_str(&format!("{:x}  {}\n", Sha256::digest(&bytes), file_name));
+            libraries.push(path);
+        }
+    }
+
+    write_atomic(&profile_dir.join("checksums.txt"), &manifest)?;
+    Ok(libraries)
+}
+
+pub fn sign_libraries(libraries: &[PathBuf], minisign_key: &str) -> Result<(), String> {
+    for library in libraries {
+        // The key must be passwordless (`minisign -G -W`), there is no terminal to prompt on
+        let status = Command::new("minisign")
+            .arg("-S")
+            .arg("-s")
+            .arg(minisign_key)
+            .arg("-m")
+            .arg(library)
+            .stdin(Stdio::null())
+            .status()
+            .map_err(|err| format!("Failed to start minisign: {}", err))?;
+
+        if !status.success() {
+            return Err(format!("minisign failed for {}", library.display()));
+        }
+    }
+    Ok(())
+}
+
+pub fn run_streamed(command: Command, log: &Arc<Mutex<Log>>, cancel: &AtomicBool) -> std::io::Result<ExitStatus> {
+    run_streamed_with(command, log, cancel, &|_| false)
+}
+
+// `consume_stdout` sees each stdout line first, the ones it returns true for aren't logged
+fn run_streamed_with(
+    mut command: Command,
+    log: &Arc<Mutex<Log>>,
+    cancel: &AtomicBool,
+    consume_stdout: &(dyn Fn(&str) -> bool + Sync),
+) -> std::io::Result<ExitStatus> {
+    // A process group of its own lets a cancel reach everything the child started
+    #[cfg(unix)]
+    std::os::unix::process::CommandExt::process_group(&mut command, 0);
+    let mut child = command.stdout(Stdio::piped()).stderr(Stdio::piped()).spawn()?;
+
+    // Cargo reports progress on stderr, so both pipes have to be drained as lines arrive,
+    // the scope joins the readers once the pipes close
+    thread::scope(|scope| {
+        if let Some(stdout) = child.stdout.take() {
+            scope.spawn(move || read_log_lines(stdout, log, consume_stdout));
+        }
+        if let Some(stderr) = child.stderr.take() {
+            scope.spawn(move || read_log_lines(stderr, log, &|_| false));
+        }
+
+        // Poll instead of blocking on wait() so a cancel request can kill the child
+        loop {
+            if let Some(status) = child.try_wait()? {
+                return Ok(status);
+            }
+            if cancel.load(Ordering::Relaxed) {
+                kill_process_tree(&mut child);
+                return Err(std::io::Error::from(ErrorKind::Interrupted));
+            }
+            thread::sleep(Duration::from_millis(100));
+        }
+    })
+}
+
+// cargo runs rustc and build scripts as its own children, killing only cargo would leave them running
+fn kill_process_tree(child: &mut Child) {
+    let pid = child.id().to_string();
+    let mut kill = if cfg!(windows) {
+        let mut kill = Command::new("taskkill");
+        kill.args(["/T", "/F", "/PID", &pid]);
+        kill
+    } else {
+        let mut kill = Command::new("kill");
+        kill.args(["-KILL", "--", &format!("-{}", pid)]);
+        kill
+    };
+    let _ = kill.stdout(Stdio::null()).stderr(Stdio::null()).status();
+    // The group kill is best effort, the child itself always goes
+    let _ = child.kill();
+    let _ = child.wait();
+}
+
+fn read_log_lines(pipe: impl Read, log: &Mutex<Log>, consume: &(dyn Fn(&str) -> bool + Sync)) {
+    let mut level = LogLevel::Output;
+    for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+        if consume(&line) {
+            continue;
+        }
+        level = output_level(&line, level);
+        log.lock().unwrap().push(level, &line);
+    }
+}
+
+// The packages a build for `triple` compiles, 0 when cargo metadata fails, it also resolves a missing Cargo.lock
+fn count_packages(options: &ProjectOptions, crate_dir: &Path, triple: Option<&str>) -> usize {
+    let mut command = Command::new(options.cargo_program());
+    command.envs(&options.env).args(["metadata", "--format-version", "1"]).current_dir(crate_dir);
+    if let Some(triple) = triple.map(str::to_string).or_else(detect_host_triple) {
+        command.args(["--filter-platform", &triple]);
+    }
+    command
+        .stdin(Stdio::null())
+        .stderr(Stdio::null())
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| serde_json::from_slice::<CargoMetadata>(&output.stdout).ok())
+        .and_then(|metadata| metadata.resolve)
+        .map_or(0, |resolve| resolve.nodes.len())
+}
+
+fn project_command(program: &str, project_name: &str) -> Result<Command, CreateError> {
+    let metadata = ProjectMetadata::load(&find_project_dir(project_name))?;
+    let mut command = Command::new(program);
+    command.envs(&metadata.env);
+    Ok(command)
+}
+
+// The Godot executable and environment the options configure
+fn godot_command(options: &ProjectOptions) -> Command {
+    let mut command = Command::new(options.godot_program());
+    command.envs(&options.env);
+    command
+}
+
+// Without a triple cargo builds for the host into target/<profile>, with one into target/<triple>/<profile>.
+// Cargo, its arguments, the environment, the target dir and the cross builder all come from `options`
+fn cargo_build(
+    project_name: &str,
+    options: &ProjectOptions,
+    triple: Option<&str>,
+    profile: &str,
+    log: &Arc<Mutex<Log>>,
+    cancel: &AtomicBool,
+    on_progress: &(dyn Fn(usize, usize) + Sync),
+) -> Result<ExitStatus, CreateError> {
+    let crate_dir = find_project_dir(project_name).join(find_crate_dir(project_name));
+    let (program, subcommand) = match (triple, options.cross_builder) {
+        (Some(_), CrossBuilder::Cross) => ("cross", "build"),
+        (Some(_), CrossBuilder::Zigbuild) => (options.cargo_program(), "zigbuild"),
+        _ => (options.cargo_program(), "build"),
+    };
+    let mut command = Command::new(program);
+    command.envs(&options.env).arg(subcommand).args(profile_args(profile));
+    if let Some(triple) = triple {
+        command.args(["--target", triple]);
+    }
+    // Relative to the crate, which is where cargo runs, and ahead of a CARGO_TARGET_DIR set in the environment
+    if options.has_custom_target_dir() {
+        command.args(["--target-dir", options.target_dir()]);
+    }
+    // Diagnostics stay rendered text on stderr, stdout turns into JSON lines that drive the progress
+    command
+        .arg("--message-format=json-render-diagnostics")
+        .args(&options.cargo_args)
+        .current_dir(&crate_dir);
+
+    let total = count_packages(options, &crate_dir, triple);
+    on_progress(0, total);
+    let built = Mutex::new(BTreeSet::new());
+    let consume_stdout = |line: &str| {
+        let Ok(message) = serde_json::from_str::<CargoMessage>(line) else {
+            return false;
+        };
+        if message.reason == "compiler-artifact" {
+            let mut built = built.lock().unwrap();
+            built.insert(message.package_id);
+            on_progress(built.len(), total);
+        }
+        true
+    };
+    match run_streamed_with(command, log, cancel, &consume_stdout) {
+        Ok(status) => Ok(status),
+        Err(err) if err.kind() == ErrorKind::Interrupted => Err(CreateError::Cancelled),
+        Err(err) => Err(CreateError::SpawnCargo(err)),
+    }
+}
+
+// Builds every selected target into the target/<profile> path the .gdextension points at, `builds` follows along per target
+pub fn precompile_library(
+    project_name: &str,
+    log: &Arc<Mutex<Log>>,
+    options: &ProjectOptions,
+    cancel: &AtomicBool,
+    builds: &Mutex<Vec<TargetBuild>>,
+) -> Result<(), CreateError> {
+    {
+        let mut log_inner = log.lock().unwrap();
+        log_inner.info("Compiling Rust library...");
+    }
+
+    let crate_dir = find_project_dir(project_name).join(find_crate_dir(project_name));
+    if fs::metadata(crate_dir.join("src/lib.rs")).is_err() || options.targets.is_empty() {
+        return Err(CreateError::MissingLibrary);
+    }
+    let names = &ProjectNames::load(project_name);
+    let target_dir = crate_dir.join(options.target_dir());
+    let crates = workspace_crates(names, &options.extension_crates);
+
+    *builds.lock().unwrap() = options
+        .targets
+        .iter()
+        .map(|target| TargetBuild {
+            target: target.clone(),
+            status: TargetStatus::Pending,
+            crates_built: 0,
+            crates_total: 0,
+        })
+        .collect();
+    let set_status = |index: usize, status: TargetStatus| builds.lock().unwrap()[index].status = status;
+
+    let host_triple = detect_host_triple().unwrap_or_default();
+    let mut built_profiles = BTreeSet::new();
+    for (index, target) in options.targets.iter().enumerate() {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(CreateError::Cancelled);
+        }
+        let Some(profile) = options.target_profile(target) else {
+            set_status(index, TargetStatus::Failed("unknown target".to_string()));
+            continue;
+        };
+
+        set_status(index, TargetStatus::Building);
+        log.lock().unwrap().info(&format!("Building {}...", target));
+        let on_progress = |built: usize, total: usize| {
+            let mut builds = builds.lock().unwrap();
+            builds[index].crates_built = built;
+            builds[index].crates_total = total;
+        };
+        let built = if is_native_target(target, &host_triple) {
+            cargo_build(project_name, options, None, &profile, log, cancel, &on_progress).map(|status| status.success())
+        } else {
+            build_cross_target(project_name, &crates, target, options, log, cancel, &on_progress)
+        };
+        match built {
+            Ok(true) => {
+                set_status(index, TargetStatus::Built);
+                built_profiles.insert(profile);
+            }
+            Ok(false) => set_status(index, TargetStatus::Failed("build failed".to_string())),
+            Err(CreateError::Cancelled) => return Err(CreateError::Cancelled),
+            Err(err) => set_status(index, TargetStatus::Failed(err.to_string())),
+        }
+    }
+
+    let failed: Vec<String> = builds
+        .lock()
+        .unwrap()
+        .iter()
+        .filter(|build| build.status != TargetStatus::Built)
+        .map(|build| build.target.clone())
+        .collect();
+    log.lock()
+        .unwrap()
+        .info(&format!("Built {} of {} targets.", options.targets.len() - failed.len(), options.targets.len()));
+
+    for profile in built_profiles {
+        match write_checksums(&target_dir.join(&profile), &crates) {
+            Ok(libraries) => {
+                log.lock()
+                    .unwrap()
+                    .info(&format!("Wrote {} checksums.txt for {} libraries.", profile, libraries.len()));
+
+                if options.sign_libraries {
+                    match sign_libraries(&libraries, &options.minisign_key) {
+                        Ok(()) => log.lock().unwrap().info("Signed libraries with minisign."),
+                        Err(err) => log.lock().unwrap().warn(&format!("Failed to sign libraries: {}", err)),
+                    }
+                }
+            }
+            Err(err) => log.lock().unwrap().warn(&format!("Failed to write checksums: {}", err)),
+        }
+    }
+
+    if options.library_layout == LibraryLayout::Bin {
+        let copied = copy_built_libraries(project_name, &crates, &target_dir, options);
+        log.lock().unwrap().info(&format!("Copied {} libraries into the project's bin folder.", copied));
+    }
+
+    if !failed.is_empty() {
+        log.lock().unwrap().error(&format!("Failed targets: {}", failed.join(", ")));
+        return Err(CreateError::BuildFailed);
+    }
+    log.lock().unwrap().info("Library built.");
+    Ok(())
+}
+
+// Builds each triple of a target and puts the libraries where a host build would have, macOS triples are merged with lipo
+fn build_cross_target(
+    project_name: &str,
+    crates: &[ProjectNames],
+    target: &str,
+    options: &ProjectOptions,
+    log: &Arc<Mutex<Log>>,
+    cancel: &AtomicBool,
+    on_progress: &(dyn Fn(usize, usize) + Sync),
+) -> Result<bool, CreateError> {
+    let Some(profile) = options.target_profile(target) else {
+        return Ok(false);
+    };
+    let triples = rust_target_triples(target);
+    for triple in &triples {
+        if !cargo_build(project_name, options, Some(triple), &profile, log, cancel, on_progress)?.success() {
+            return Ok(false);
+        }
+    }
+
+    let crate_dir = find_project_dir(project_name).join(find_crate_dir(project_name));
+    let target_dir = crate_dir.join(options.target_dir());
+    for names in crates {
+        let Some(file) = library_file(names, target) else {
+            return Ok(false);
+        };
+        if !merge_triple_outputs(&target_dir, &triples, &profile, &file, log)? {
+            return Ok(false);
+        }
+    }
+    Ok(true)
+}
+
+fn merge_triple_outputs(target_dir: &Path, triples: &[String], profile: &str, file: &str, log: &Arc<Mutex<Log>>) -> Result<bool, CreateError> {
+    let outputs: Vec<PathBuf> = triples.iter().map(|triple| target_dir.join(triple).join(profile).join(file)).collect();
+    let destination = target_dir.join(profile).join(file);
+    let write_error = |source| CreateError::WriteFile {
+        path: destination.clone(),
+        source,
+    };
+    fs::create_dir_all(target_dir.join(profile)).map_err(write_error)?;
+    match outputs.as_slice() {
+        [output] => fs::copy(output, &destination).map(|_| true).map_err(write_error),
+        outputs => {
+            let merged = Command::new("lipo").arg("-create").args(outputs).arg("-output").arg(&destination).status();
+            match merged {
+                Ok(status) => Ok(status.success()),
+                // Without lipo the first architecture stands in for the universal library
+                Err(_) => {
+                    log.lock()
+                        .unwrap()
+                        .warn(&format!("lipo is not available, {} only contains {}.", file, triples[0]));
+                    fs::copy(&outputs[0], &destination).map(|_| true).map_err(write_error)
+                }
+            }
+        }
+    }
+}
+
+// Same copies as the generated copy_libs.sh, libraries that weren't built are skipped
+fn copy_built_libraries(project_name: &str, crates: &[ProjectNames], target_dir: &Path, options: &ProjectOptions) -> usize {
+    let project_dir = find_project_dir(project_name);
+    crates
+        .iter()
+        .flat_map(|names| options.targets.iter().map(move |target| (names, target)))
+        .filter_map(|(names, target)| {
+            let (profile, file) = (options.target_profile(target)?, library_file(names, target)?);
+            let destination = project_dir.join(copied_library_path(names, target)?);
+            fs::create_dir_all(destination.parent()?).ok()?;
+            fs::copy(target_dir.join(profile).join(file), destination).ok()
+        })
+        .count()
+}
+
+pub fn gdextension_relative_path(project_name: &str, flavor: ProjectFlavor) -> String {
+    match flavor {
+        ProjectFlavor::Game => format!("{}.gdextension", project_name),
+        ProjectFlavor::Addon => format!("addons/{0}/{0}.gdextension", project_name),
+    }
+}
+
+// Next to the main crate's .gdextension, named after the extension crate
+fn extension_gdextension_path(project_name: &str, crate_name: &str, flavor: ProjectFlavor) -> String {
+    match flavor {
+        ProjectFlavor::Game => format!("{}.gdextension", crate_name),
+        ProjectFlavor::Addon => format!("addons/{}/{}.gdextension", project_name, crate_name),
+    }
+}
+
+// Existing projects don't record their flavor, so both locations are tried
+pub fn find_gdextension(project_name: &str) -> PathBuf {
+    let project_dir = find_project_dir(project_name);
+    let addon_path = project_dir.join(gdextension_relative_path(project_name, ProjectFlavor::Addon));
+    if addon_path.exists() {
+        addon_path
+    } else {
+        project_dir.join(gdextension_relative_path(project_name, ProjectFlavor::Game))
+    }
+}
+
+// Run as the main loop of the editor the stress test keeps open. Reloads the extension each time the tool writes a new
+// iteration into the trigger file and prints the outcome, "quit" ends it
+const RELOAD_DRIVER_SCRIPT: &str = r#"extends SceneTree
+
+const TRIGGER := "res://.godot/gen_gdext_reload_trigger"
+
+var extension_path := ""
+var handled := ""
+
+
+func _initialize() -> void:
+	extension_path = OS.get_cmdline_user_args()[0]
+	# A project the editor hasn't listed the extension for yet loads it here
+	if not GDExtensionManager.is_extension_loaded(extension_path):
+		GDExtensionManager.load_extension(extension_path)
+	print("gen_gdext ready")
+
+
+func _process(_delta: float) -> bool:
+	var trigger := FileAccess.get_file_as_string(TRIGGER).strip_edges()
+	if trigger.is_empty() or trigger == handled:
+		return false
+	handled = trigger
+	if trigger == "quit":
+		return true
+	print("gen_gdext reload %s %d" % [trigger, GDExtensionManager.reload_extension(extension_path)])
+	return false
+"#;
+const RELOAD_DRIVER_FILE: &str = ".godot/gen_gdext_reload.gd";
+const RELOAD_TRIGGER_FILE: &str = ".godot/gen_gdext_reload_trigger";
+const RELOAD_READY: &str = "gen_gdext ready";
+const RELOAD_STATUS_PREFIX: &str = "gen_gdext reload ";
+// Long enough for the first start on a project the editor still has to import
+const RELOAD_TIMEOUT: Duration = Duration::from_secs(120);
+
+// Rebuilds the library while one headless editor keeps it loaded, every rebuild has to hot reload there without loader errors
+pub fn stress_test_reload(
+    project_name: &str,
+    options: &ProjectOptions,
+    iterations: u32,
+    log: &Arc<Mutex<Log>>,
+    cancel: &AtomicBool,
+) -> Result<(), CreateError> {
+    let project_dir = find_project_dir(project_name);
+    let crate_dir = find_crate_dir(project_name);
+    let lib_path = project_dir.join(&crate_dir).join("src/lib.rs");
+    if !lib_path.exists() {
+        return Err(CreateError::MissingLibrary);
+    }
+
+    let host_triple = detect_host_triple().unwrap_or_default();
+    let target = reload_target(options, &host_triple).ok_or(CreateError::NoHostTarget)?;
+    let profile = options.target_profile(target).ok_or(CreateError::NoHostTarget)?;
+    let names = ProjectNames::load(project_name);
+    let crates = workspace_crates(&names, &options.extension_crates);
+    let target_dir = project_dir.join(&crate_dir).join(options.target_dir());
+
+    let gdextension_path = find_gdextension(project_name);
+    let invalid_gdextension = |message: String| CreateError::InvalidGdExtension {
+        path: gdextension_path.clone(),
+        message,
+    };
+    let content = fs::read_to_string(&gdextension_path).map_err(|err| invalid_gdextension(err.to_string()))?;
+    let file = GdExtensionFile::parse(&content).map_err(invalid_gdextension)?;
+    if file.get("configuration", "reloadable") != Some("true") {
+        log.lock().unwrap().warn(&format!(
+            "{} is not marked reloadable = true, the editor will not hot reload it.",
+            gdextension_path.display()
+        ));
+    }
+    // A library the .gdextension doesn't load from never changes for the editor, and every reload would pass
+    let rebuilt = rebuilt_library_path(&names, &crate_dir, options, target).ok_or(CreateError::NoHostTarget)?;
+    let loaded = editor_library_entry(&file, options, target).unwrap_or_default();
+    if loaded != rebuilt {
+        return Err(CreateError::ReloadLibraryMismatch {
+            path: gdextension_path,
+            target: target.to_string(),
+            loaded,
+            rebuilt,
+        });
+    }
+    let relative = gdextension_path.strip_prefix(&project_dir).unwrap_or(&gdextension_path);
+    let gdextension = resource_path(&relative.to_string_lossy().replace('\\', "/"));
+
+    log.lock().unwrap().info("Starting a headless editor that keeps the library loaded...");
+    let editor = ReloadEditor::start(project_name, options, &gdextension)?;
+    let (_, errors) = editor
+        .wait_for(|line| line == RELOAD_READY, log, cancel)?
+        .ok_or(CreateError::EditorUnresponsive)?;
+    log_loader_errors(log, &errors);
+
+    let mut failed = 0;
+    for iteration in 1..=iterations {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(CreateError::Cancelled);
+        }
+        log.lock()
+            .unwrap()
+            .info(&format!("Reload iteration {}/{}: rebuilding...", iteration, iterations));
+
+        // Bumping the mtime makes cargo relink, so every iteration gives the editor a new library to reload
+        fs::File::options()
+            .write(true)
+            .open(&lib_path)
+            .and_then(|file| file.set_modified(SystemTime::now()))
+            .map_err(|source| CreateError::WriteFile {
+                path: lib_path.clone(),
+                source,
+            })?;
+        if !cargo_build(project_name, options, None, &profile, log, cancel, &|_, _| {})?.success() {
+            return Err(CreateError::BuildFailed);
+        }
+        if options.library_layout != LibraryLayout::TargetDir {
+            copy_built_libraries(project_name, &crates, &target_dir, options);
+        }
+
+        editor.trigger(&iteration.to_string())?;
+        let prefix = format!("{}{} ", RELOAD_STATUS_PREFIX, iteration);
+        let (line, mut errors) = editor
+            .wait_for(|line| line.starts_with(&prefix), log, cancel)?
+            .ok_or(CreateError::EditorUnresponsive)?;
+        if let Some(problem) = reload_status_problem(&line[prefix.len()..]) {
+            errors.push(problem);
+        }
+        if errors.is_empty() {
+            log.lock().unwrap().info("  Library reloaded without errors.");
+        } else {
+            failed += 1;
+            log_loader_errors(log, &errors);
+        }
+    }
+
+    if failed > 0 {
+        return Err(CreateError::ReloadFailed { failed, iterations });
+    }
+    log.lock().unwrap().info(&format!("All {} reload iterations reloaded cleanly.", iterations));
+    Ok(())
+}
+
+// The host's target the editor loads, a debug one where there is one
+fn reload_target<'a>(options: &'a ProjectOptions, host_triple: &str) -> Option<&'a str> {
+    let mut native = options.targets.iter().filter(|target| is_native_target(target, host_triple));
+    let first = native.clone().next();
+    native.find(|target| default_profile(target) == Some("debug")).or(first).map(String::as_str)
+}
+
+// The library the stress test rebuilds, after the copy step for the bin/ layout
+fn rebuilt_library_path(names: &ProjectNames, crate_dir: &str, options: &ProjectOptions, target: &str) -> Option<String> {
+    Some(match options.library_layout {
+        LibraryLayout::TargetDir | LibraryLayout::Split => resource_path(&built_library_path(names, crate_dir, options, target)?),
+        LibraryLayout::Bin => format!("res://{}", copied_library_path(names, target)?),
+    })
+}
+
+// The path the editor loads a target's library from, the split layout's editor entry wins like it does in Godot
+fn editor_library_entry(file: &GdExtensionFile, options: &ProjectOptions, target: &str) -> Option<String> {
+    let editor_entry = (options.library_layout == LibraryLayout::Split)
+        .then(|| file.get("libraries", &editor_feature_tags(target)))
+        .flatten();
+    editor_entry.or_else(|| file.get("libraries", target)).map(unquote)
+}
+
+fn log_loader_errors(log: &Arc<Mutex<Log>>, errors: &[String]) {
+    let mut log_inner = log.lock().unwrap();
+    for error in errors {
+        log_inner.error(&format!("  {}", error));
+    }
+}
+
+// GDExtensionManager.LoadStatus as the driver prints it, None for OK
+fn reload_status_problem(status: &str) -> Option<String> {
+    let problem = match status.trim() {
+        "0" => return None,
+        "1" => "the reload failed",
+        "3" => "the extension isn't loaded",
+        "4" => "the editor needs a restart to pick up the change",
+        other => return Some(format!("reload_extension returned status {}", other)),
+    };
+    Some(format!("reload_extension: {}", problem))
+}
+
+// The headless editor the stress test reloads the library in, killed on drop so neither an error nor a cancel leaves it running
+struct ReloadEditor {
+    child: Child,
+    lines: mpsc::Receiver<String>,
+    driver_path: PathBuf,
+    trigger_path: PathBuf,
+}
+
+impl ReloadEditor {
+    fn start(project_name: &str, options: &ProjectOptions, gdextension: &str) -> Result<Self, CreateError> {
+        let project_dir = find_project_dir(project_name);
+        let driver_path = project_dir.join(RELOAD_DRIVER_FILE);
+        let trigger_path = project_dir.join(RELOAD_TRIGGER_FILE);
+        let write_error = |path: &Path| {
+            let path = path.to_path_buf();
+            move |source| CreateError::WriteFile { path, source }
+        };
+        fs::create_dir_all(project_dir.join(".godot")).map_err(write_error(&driver_path))?;
+        write_atomic(&driver_path, RELOAD_DRIVER_SCRIPT).map_err(write_error(&driver_path))?;
+        write_atomic(&trigger_path, "").map_err(write_error(&trigger_path))?;
+
+        let mut command = godot_command(options);
+        // Godot only allows reloading extensions in the editor, the driver script replaces its main loop
+        command
+            .args(["--headless", "--editor", "--path"])
+            .arg(&project_dir)
+            .args(["--script", &resource_path(RELOAD_DRIVER_FILE), "--", gdextension]);
+        #[cfg(unix)]
+        std::os::unix::process::CommandExt::process_group(&mut command, 0);
+        let mut child = command
+            .stdin(Stdio::null())
+            .stdout(Stdio::piped())
+            .stderr(Stdio::piped())
+            .spawn()
+            .map_err(CreateError::SpawnGodot)?;
+
+        let (sender, lines) = mpsc::channel();
+        if let Some(stdout) = child.stdout.take() {
+            forward_lines(stdout, sender.clone());
+        }
+        if let Some(stderr) = child.stderr.take() {
+            forward_lines(stderr, sender);
+        }
+        Ok(Self {
+            child,
+            lines,
+            driver_path,
+            trigger_path,
+        })
+    }
+
+    fn trigger(&self, value: &str) -> Result<(), CreateError> {
+        write_atomic(&self.trigger_path, value).map_err(|source| CreateError::WriteFile {
+            path: self.trigger_path.clone(),
+            source,
+        })
+    }
+
+    // The editor's output up to the line `is_done` accepts, with the errors printed on the way.
+    // None when the editor exits or stays silent for RELOAD_TIMEOUT
+    fn wait_for(&self, is_done: impl Fn(&str) -> bool, log: &Arc<Mutex<Log>>, cancel: &AtomicBool) -> Result<Option<(String, Vec<String>)>, CreateError> {
+        let mut errors = Vec::new();
+        let mut last_output = Instant::now();
+        loop {
+            if cancel.load(Ordering::Relaxed) {
+                return Err(CreateError::Cancelled);
+            }
+            match self.lines.recv_timeout(Duration::from_millis(100)) {
+                Ok(line) => {
+                    last_output = Instant::now();
+                    if is_done(&line) {
+                        return Ok(Some((line, errors)));
+                    }
+                    if line.contains("ERROR") {
+                        errors.push(line.trim().to_string());
+                    } else {
+                        log.lock().unwrap().push(LogLevel::Output, &line);
+                    }
+                }
+                Err(mpsc::RecvTimeoutError::Timeout) if last_output.elapsed() < RELOAD_TIMEOUT => {}
+                Err(_) => return Ok(None),
+            }
+        }
+    }
+}
+
+impl Drop for ReloadEditor {
+    fn drop(&mut self) {
+        kill_process_tree(&mut self.child);
+        let _ = self.child.wait();
+        let _ = fs::remove_file(&self.driver_path);
+        let _ = fs::remove_file(&self.trigger_path);
+    }
+}
+
+fn forward_lines(pipe: impl Read + Send + 'static, sender: mpsc::Sender<String>) {
+    thread::spawn(move || {
+        for line in BufReader::new(pipe).lines().map_while(Result::ok) {
+            if sender.send(line).is_err() {
+                break;
+            }
+        }
+    });
+}
+
+// Starts the editor on the project without waiting for it, the editor outlives the tool
+pub fn open_in_editor(project_name: &str) -> Result<(), CreateError> {
+    let options = ProjectMetadata::load(&find_project_dir(project_name))?.saved_options();
+    let mut command = godot_command(&options);
+    command.args(["--editor", "--path"]).arg(find_project_dir(project_name));
+    command.spawn().map(|_| ()).map_err(CreateError::SpawnGodot)
+}
+
+// Shows the folder in the platform's file manager
+pub fn open_folder(path: &Path) -> Result<(), CreateError> {
+    let program = if cfg!(windows) {
+        "explorer"
+    } else if cfg!(target_os = "macos") {
+        "open"
+    } else {
+        "xdg-open"
+    };
+    Command::new(program).arg(path).spawn().map(|_| ()).map_err(CreateError::SpawnFileManager)
+}
+
+// Opens the crate folder in the configured code editor, which like Godot outlives the tool
+pub fn open_in_code_editor(project_name: &str) -> Result<(), CreateError> {
+    let metadata = ProjectMetadata::load(&find_project_dir(project_name))?;
+    let mut words = metadata.editor_command().split_whitespace();
+    let program = match words.next() {
+        // VS Code installs a batch file on Windows, which Command only finds with its extension
+        Some(CODE_EDITOR) if cfg!(windows) => "code.cmd",
+        Some(program) => program,
+        None => CODE_EDITOR,
+    };
+
+    let project_dir = find_project_dir(project_name);
+    let crate_dir = find_crate_dir(project_name);
+    let path = absolute_crate_dir(&project_dir, &crate_dir).unwrap_or_else(|| project_dir.join(&crate_dir));
+    let mut command = Command::new(program);
+    command.envs(&metadata.env).args(words).arg(path);
+    command.spawn().map(|_| ()).map_err(CreateError::SpawnEditor)
+}
+
+// The manifest of the Cargo workspace the new crate would sit in, if any
+pub fn find_cargo_workspace(project_dir: &Path, crate_dir: &str) -> Option<PathBuf> {
+    absolute_crate_dir(project_dir, crate_dir)?
+        .ancestors()
+        .skip(1)
+        .map(|dir| dir.join("Cargo.toml"))
+        .find(|manifest| fs::read_to_string(manifest).is_ok_and(|content| content.lines().any(|line| line.trim() == "[workspace]")))
+}
+
+fn workspace_member_path(manifest: &Path, project_dir: &Path, crate_dir: &str) -> Option<String> {
+    let workspace_dir = manifest.parent()?;
+    let crate_dir = absolute_crate_dir(project_dir, crate_dir)?;
+    let relative = crate_dir.strip_prefix(workspace_dir).ok()?;
+    Some(
+        relative
+            .components()
+            .map(|component| component.as_os_str().to_string_lossy())
+            .collect::<Vec<_>>()
+            .join("/"),
+    )
+}
+
+// What adding a crate to a workspace manifest came to
+#[derive(Debug, PartialEq)]
+enum WorkspaceMembership {
+    Listed,
+    // The manifest with the crate added to [workspace] members
+    Added(String),
+    // No [workspace] section, or a members value that isn't a plain array
+    Unchanged,
+}
+
+// Adds `member` to the [workspace] members list, only the strings in that list count as listed
+fn add_workspace_member(manifest: &str, member: &str) -> WorkspaceMembership {
+    let mut lines = Vec::new();
+    let mut offset = 0;
+    for line in manifest.split_inclusive('\n') {
+        lines.push((offset, line));
+        offset += line.len();
+    }
+    let is_header = |line: &str| line.trim_start().starts_with('[');
+    let Some(workspace) = lines.iter().position(|(_, line)| strip_toml_comment(line).trim() == "[workspace]") else {
+        return WorkspaceMembership::Unchanged;
+    };
+    let section = lines[workspace + 1..].iter().take_while(|(_, line)| !is_header(line));
+    let members = section.clone().find(|(_, line)| {
+        line.trim_start()
+            .strip_prefix("members")
+            .is_some_and(|rest| rest.trim_start().starts_with('='))
+    });
+    let quoted = format!("\"{}\"", member);
+
+    let Some((members, _)) = members else {
+        let (header, header_line) = lines[workspace];
+        let newline = if header_line.ends_with('\n') { "" } else { "\n" };
+        let mut content = manifest.to_string();
+        content.insert_str(header + header_line.len(), &format!("{}members = [{}]\n", newline, quoted));
+        return WorkspaceMembership::Added(content);
+    };
+    let Some(open) = manifest[*members..].find('=').and_then(|equals| {
+        let value = members + equals + 1;
+        let start = value + manifest[value..].len() - manifest[value..].trim_start().len();
+        manifest[start..].starts_with('[').then_some(start)
+    }) else {
+        return WorkspaceMembership::Unchanged;
+    };
+    let Some(array) = scan_toml_array(&manifest[open + 1..]) else {
+        return WorkspaceMembership::Unchanged;
+    };
+    if array.strings.iter().any(|listed| listed == member) {
+        return WorkspaceMembership::Listed;
+    }
+
+    let close = open + 1 + array.close;
+    let last = open + 1 + array.last_significant.unwrap_or(0);
+    let after_last = if array.last_significant.is_some() { last + 1 } else { open + 1 };
+    let needs_comma = array.last_significant.is_some() && !manifest[last..].starts_with(',');
+    let mut content = manifest.to_string();
+    // A ] on a line of its own gets the new member on the line above it, the list stays one entry per line
+    let line_start = manifest[..close].rfind('\n').map_or(0, |newline| newline + 1);
+    if line_start > after_last && manifest[line_start..close].trim().is_empty() {
+        content.insert_str(line_start, &format!("    {},\n", quoted));
+    } else if array.last_significant.is_some() {
+        content.insert_str(after_last, &format!(" {}", quoted));
+    } else {
+        content.insert_str(after_last, &quoted);
+    }
+    if needs_comma {
+        content.insert(after_last, ',');
+    }
+    WorkspaceMembership::Added(content)
+}
+
+fn strip_toml_comment(line: &str) -> &str {
+    let mut in_string = None;
+    let mut escaped = false;
+    for (index, c) in line.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string == Some('"') => escaped = true,
+            '"' | '\'' if in_string.is_none() => in_string = Some(c),
+            c if in_string == Some(c) => in_string = None,
+            '#' if in_string.is_none() => return &line[..index],
+            _ => {}
+        }
+    }
+    line
+}
+
+// The strings of a TOML array of strings starting after its [, with where it closes and its last character outside comments
+struct TomlArray {
+    strings: Vec<String>,
+    close: usize,
+    last_significant: Option<usize>,
+}
+
+fn scan_toml_array(content: &str) -> Option<TomlArray> {
+    let mut strings = Vec::new();
+    let mut last_significant = None;
+    let mut current: Option<(char, String)> = None;
+    let mut escaped = false;
+    let mut in_comment = false;
+    for (index, c) in content.char_indices() {
+        if in_comment {
+            in_comment = c != '\n';
+            continue;
+        }
+        if let Some((quote, value)) = &mut current {
+            match c {
+                _ if escaped => {
+                    escaped = false;
+                    value.push(c);
+                }
+                '\\' if *quote == '"' => escaped = true,
+                c if c == *quote => {
+                    strings.push(std::mem::take(value));
+                    current = None;
+                    last_significant = Some(index);
+                }
+                c => value.push(c),
+            }
+            continue;
+        }
+        match c {
+            '"' | '\'' => current = Some((c, String::new())),
+            '#' => {
+                in_comment = true;
+                continue;
+            }
+            ']' => {
+                return Some(TomlArray {
+                    strings,
+                    close: index,
+                    last_significant,
+                })
+            }
+            c if c.is_whitespace() => continue,
+            _ => {}
+        }
+        last_significant = Some(index);
+    }
+    None
+}
+
+fn join_cargo_workspace(writer: &ProjectWriter, project_name: &str, options: &ProjectOptions) -> Result<(), CreateError> {
+    let crate_dir = crate_relative_dir(project_name, options.crate_location);
+    let Some(manifest) = find_cargo_workspace(&writer.root, &crate_dir) else {
+        return Ok(());
+    };
+    let Some(member) = workspace_member_path(&manifest, &writer.root, &crate_dir) else {
+        return Ok(());
+    };
+
+    let content = fs::read_to_string(&manifest).map_err(|source| CreateError::WriteFile {
+        path: manifest.clone(),
+        source,
+    })?;
+    let membership = add_workspace_member(&content, &member);
+    if let WorkspaceMembership::Added(content) = &membership {
+        writer.update_file(&manifest, content)?;
+    }
+
+    let mut log_inner = writer.log.lock().unwrap();
+    match membership {
+        WorkspaceMembership::Listed | WorkspaceMembership::Added(_) => {
+            log_inner.info(&format!("{} is a member of the Cargo workspace at {}.", member, manifest.display()))
+        }
+        WorkspaceMembership::Unchanged => log_inner.warn(&format!(
+            "Couldn't add {} to the members of the Cargo workspace at {}, add it by hand.",
+            member,
+            manifest.display()
+        )),
+    }
+    log_inner.warn(&format!(
+        "{0}/.cargo/config.toml keeps the target dir at {0}/target for the .gdextension paths, \
+         libraries built from the workspace root land in the workspace target dir instead.",
+        crate_dir
+    ));
+    if options.minimal_size || !options.release_profile.is_empty() {
+        log_inner.warn("Cargo ignores profiles in workspace members, add the release profile to the workspace manifest.");
+    }
+    let custom_profiles: BTreeSet<String> = options
+        .targets
+        .iter()
+        .filter_map(|target| options.target_profile(target))
+        .filter(|profile| !is_builtin_profile(profile))
+        .collect();
+    if !custom_profiles.is_empty() {
+        log_inner.warn(&format!(
+            "Cargo ignores profiles in workspace members, add [profile.{}] to the workspace manifest.",
+            custom_profiles.into_iter().collect::<Vec<_>>().join("], [profile.")
+        ));
+    }
+    Ok(())
+}
+
+pub fn render_project_files(project_name: &str, templates: &ProjectTemplates, options: &ProjectOptions) -> Result<Vec<(String, String)>, CreateError> {
+    let crate_dir = crate_relative_dir(project_name, options.crate_location);
+    let names = &ProjectNames::new(project_name, options);
+    let crates = workspace_crates(names, &options.extension_crates);
+    // Every template is filled once with these and its own values, output is never filled again
+    let variables = &template_variables(project_name, names, options);
+    let class_icons = if options.class_icons {
+        get_class_icons(templates, names, project_name, options, variables)?
+    } else {
+        Vec::new()
+    };
+    let icons: Vec<(String, String)> = class_icons
+        .iter()
+        .map(|(class_name, path, _)| (class_name.clone(), resource_path(path)))
+        .collect();
+    let gdextension_content = get_gdextension_content(templates, names, &crate_dir, options, &icons, variables)?;
+    let in_workspace = find_cargo_workspace(&project_dir(project_name, options.crate_location), &crate_dir).is_some();
+    let mut cargo_toml_content = get_cargo_toml_content(
+        templates,
+        names,
+        options.minimal_size && !in_workspace,
+        (!in_workspace).then_some(&options.release_profile),
+        &options.godot_crate_features(),
+        options.license,
+        variables,
+    )?;
+    if !options.extension_crates.is_empty() {
+        let members: Vec<String> = options.extension_crates.iter().map(|crate_name| format!("\"{}\"", crate_name)).collect();
+        cargo_toml_content.push_str(&render(
+            require_template("cargo_workspace", &templates.cargo_workspace)?,
+            variables,
+            &[("members", &members.join(", "))],
+        ));
+    }
+    if !in_workspace {
+        cargo_toml_content.push_str(&get_custom_profiles(templates, options, variables)?);
+    }
+    cargo_toml_content = add_dependencies(&cargo_toml_content, &dependency_lines(options));
+    // Lints aren't inherited by workspace members, each extension crate gets the table too
+    let lints = if options.code_style {
+        render(&templates.cargo_lints, variables, &[])
+    } else {
+        String::new()
+    };
+    cargo_toml_content.push_str(&lints);
+    let mut files = vec![
+        // Create project.godot file
+        ("project.godot".to_string(), get_project_godot_content(names, options)),
+        // Write the default project icon
+        (
+            "icon.svg".to_string(),
+            render(require_template("icon_svg", &templates.icon_svg)?, variables, &[]),
+        ),
+        // Write the Cargo.toml file
+        (format!("{}/Cargo.toml", crate_dir), cargo_toml_content),
+        // Write the .gitignore file
+        (format!("{}/.gitignore", crate_dir), get_gitignore_content(templates, options, variables)),
+        // Write lib.rs file
+        (format!("{}/src/lib.rs", crate_dir), get_lib_content(templates, names, options, variables)?),
+        // Write .gdextension file
+        (gdextension_relative_path(project_name, options.flavor), gdextension_content),
+        // Write the main scene project.godot points at
+        (
+            "main.tscn".to_string(),
+            get_main_scene_content(templates, names, options.main_base_class(), variables)?,
+        ),
+    ];
+
+    // Write each extension crate with its own manifest, entry point and .gdextension, additional classes stay in the main crate
+    let extension_options = ProjectOptions {
+        classes: Vec::new(),
+        prelude_module: false,
+        itest: false,
+        entry_symbol: String::new(),
+        library_paths: BTreeMap::new(),
+        ..options.clone()
+    };
+    for extension_names in &crates[1..] {
+        let extension_dir = format!("{}/{}", crate_dir, extension_names.crate_name);
+        files.push((
+            format!("{}/Cargo.toml", extension_dir),
+            get_cargo_toml_content(
+                templates,
+                extension_names,
+                false,
+                None,
+                &options.godot_crate_features(),
+                options.license,
+                variables,
+            )? + &lints,
+        ));
+        files.push((
+            format!("{}/src/lib.rs", extension_dir),
+            get_lib_content(templates, extension_names, &extension_options, variables)?,
+        ));
+        files.push((
+            extension_gdextension_path(project_name, &extension_names.crate_name, options.flavor),
+            get_gdextension_content(templates, extension_names, &crate_dir, &extension_options, &[], variables)?,
+        ));
+    }
+
+    // Write the addon's plugin.cfg next to its .gdextension
+    if options.flavor == ProjectFlavor::Addon {
+        let plugin_cfg = render(
+            require_template("addon_plugin_cfg", &templates.addon_plugin_cfg)?,
+            variables,
+            &[("class_name", &main_class_name(names))],
+        );
+        files.push((format!("addons/{}/plugin.cfg", project_name), plugin_cfg));
+    }
+
+    // Write one module per additional class into its layout folder, the class registry declares them all
+    check_classes(names, &options.classes)?;
+    let mut class_modules = Vec::new();
+    let mut registry_entries = String::new();
+    for class in &options.classes {
+        let Some(dir) = options.layout.module_dir(class.base_class) else {
+            continue;
+        };
+        let module = convert_to_snake_case(&class.name);
+        let path = format!("{}/src/{}/{}.rs", crate_dir, dir, module);
+        files.push((path.clone(), get_class_module_content(templates, class, options.prelude_module, variables)?));
+        class_modules.push(path);
+        registry_entries.push_str(&class_registry_entry(dir, &module));
+    }
+    if !registry_entries.is_empty() {
+        let registry = render(require_template("class_registry", &templates.class_registry)?, variables, &[]);
+        files.push((format!("{}/{}", crate_dir, CLASS_REGISTRY_FILE), format!("{}{}", registry, registry_entries)));
+    }
+
+    // Write the scripts copying the built libraries to where the .gdextension expects them, PowerShell only for Windows targets
+    if options.library_layout.uses_bin() {
+        let script = require_template("copy_artifacts_script", &templates.copy_artifacts_script)?;
+        files.push((
+            "copy_libs.sh".to_string(),
+            get_copy_script_content(script, variables, "copy", &crates, &crate_dir, options),
+        ));
+        if options.targets.iter().any(|target| target.starts_with("windows.")) {
+            let script = require_template("copy_artifacts_script_windows", &templates.copy_artifacts_script_windows)?;
+            files.push((
+                "copy_libs.ps1".to_string(),
+                get_copy_script_content(script, variables, "Copy-Library", &crates, &crate_dir, options),
+            ));
+        }
+    }
+
+    // Write the GitHub Actions workflow building every selected target, its paths are relative to the repository root
+    let repository = repository_prefix(options.crate_location);
+    if options.github_workflow {
+        let (workflow_crate_dir, workflow_project_dir) = match options.crate_location {
+            CrateLocation::Root => ("rust", ROOT_LAYOUT_GODOT_DIR),
+            CrateLocation::Inside | CrateLocation::Sibling => (crate_dir.as_str(), "."),
+        };
+        // With the bin layout the copy script runs before the upload and bin/ ships in the artifact
+        let (copy_step, bin_artifact) = match options.library_layout {
+            LibraryLayout::TargetDir => (String::new(), String::new()),
+            LibraryLayout::Bin | LibraryLayout::Split => (
+                format!(
+                    "      - name: Copy into bin\n        shell: bash\n        working-directory: {}\n        run: sh copy_libs.sh\n",
+                    workflow_project_dir
+                ),
+                match options.crate_location {
+                    CrateLocation::Root => format!("            {}/bin/\n", ROOT_LAYOUT_GODOT_DIR),
+                    CrateLocation::Inside | CrateLocation::Sibling => "            bin/\n".to_string(),
+                },
+            ),
+        };
+        let workflow = render(
+            require_template("github_workflow", &templates.github_workflow)?,
+            variables,
+            &[
+                ("matrix", &get_workflow_matrix(names, options)),
+                ("copy_step", &copy_step),
+                ("bin_artifact", &bin_artifact),
+                ("crate_dir", workflow_crate_dir),
+            ],
+        );
+        files.push((format!("{}.github/workflows/build.yml", repository), workflow));
+    }
+
+    // Write export_presets.cfg next to project.godot, so the editor can export every selected platform right away
+    if options.export_presets {
+        let presets = get_export_presets_content(templates, options, variables);
+        files.push(("export_presets.cfg".to_string(), require_template("export_presets", &presets)?.to_string()));
+    }
+
+    // Write the VS Code workspace: rust-analyzer pointed at the crate, build tasks and a debugger attached to Godot
+    if options.vscode {
+        let settings = render(
+            require_template("vscode_settings", &templates.vscode_settings)?,
+            variables,
+            &[("crate_dir", &crate_dir)],
+        );
+        let tasks = render(
+            require_template("vscode_tasks", &templates.vscode_tasks)?,
+            variables,
+            &[("tasks", &get_vscode_tasks(names, &crate_dir, options))],
+        );
+        let launch = render(
+            require_template("vscode_launch", &templates.vscode_launch)?,
+            variables,
+            &[("godot_executable", options.godot_program())],
+        );
+        files.push((".vscode/settings.json".to_string(), settings));
+        files.push((".vscode/tasks.json".to_string(), tasks));
+        files.push((".vscode/launch.json".to_string(), launch));
+    }
+
+    // Write the justfile or Makefile with a build recipe per target
+    let task_runner = match options.task_runner {
+        TaskRunner::None => None,
+        TaskRunner::Just => Some(("justfile", require_template("justfile", &templates.justfile)?, "    ")),
+        TaskRunner::Make => Some(("Makefile", require_template("makefile", &templates.makefile)?, "\t")),
+    };
+    if let Some((file_name, template, indent)) = task_runner {
+        let (recipe_names, recipes) = get_build_recipes(names, &crate_dir, options, indent);
+        let content = render(
+            template,
+            variables,
+            &[("build_recipe_names", &recipe_names.join(" ")), ("build_recipes", &recipes)],
+        );
+        files.push((file_name.to_string(), content));
+    }
+
+    // Write the LICENSE next to project.godot
+    if let Some(license) = get_license_content(templates, options.license, variables)? {
+        files.push(("LICENSE".to_string(), license));
+    }
+
+    // Write the root .gitignore covering the Godot cache, export output and cargo's target dir
+    if options.git_init || !templates.project_gitignore.trim().is_empty() {
+        let mut gitignore = render(require_template("project_gitignore", &templates.project_gitignore)?, variables, &[]);
+        if options.library_layout.uses_bin() {
+            gitignore.push_str("# Libraries copied by copy_libs\nbin/\n");
+        }
+        // One inside the crate is covered by the crate's .gitignore, one next to it still lands in the repository
+        if options.has_custom_target_dir() && !options.target_dir_in_crate() {
+            let target_dir = match options.crate_location {
+                CrateLocation::Root => format!("{}/{}", ROOT_LAYOUT_GODOT_DIR, target_dir_path(&crate_dir, options.target_dir())),
+                CrateLocation::Inside | CrateLocation::Sibling => target_dir_path(&crate_dir, options.target_dir()),
+            };
+            if let Some(target_dir) = normalize_relative_path(&target_dir) {
+                gitignore.push_str(&format!("# Cargo build output\n/{}/\n", target_dir));
+            }
+        }
+        files.push((format!("{}.gitignore", repository), gitignore));
+    }
+    if !templates.gitattributes.trim().is_empty() {
+        files.push((format!("{}.gitattributes", repository), render(&templates.gitattributes, variables, &[])));
+    }
+
+    // Write the shared prelude the class modules import from
+    if options.prelude_module {
+        files.push((
+            format!("{}/src/prelude.rs", crate_dir),
+            render(require_template("prelude_module", &templates.prelude_module)?, variables, &[]),
+        ));
+    }
+
+    // Write the crate's formatting and clippy configuration, rustfmt and clippy find them from the extension crates too
+    if options.code_style {
+        files.push((
+            format!("{}/rustfmt.toml", crate_dir),
+            render(require_template("rustfmt_toml", &templates.rustfmt_toml)?, variables, &[]),
+        ));
+        if !templates.clippy_toml.trim().is_empty() {
+            files.push((format!("{}/clippy.toml", crate_dir), render(&templates.clippy_toml, variables, &[])));
+        }
+    }
+
+    // Write the class icons the [icons] section points at
+    for (_, path, content) in &class_icons {
+        files.push((path.clone(), content.clone()));
+    }
+
+    // Write the integration tests: the Rust test runner, the scene starting it and the script running that scene headless
+    if options.itest {
+        let mut build_steps = options.cargo_build_command(&crate_dir, "debug");
+        if options.library_layout == LibraryLayout::Bin {
+            build_steps.push_str("\nsh copy_libs.sh");
+        }
+        files.push((
+            format!("{}/src/itest.rs", crate_dir),
+            render(require_template("itest_module", &templates.itest_module)?, variables, &[]),
+        ));
+        files.push((
+            "tests/itest.tscn".to_string(),
+            render(require_template("itest_scene", &templates.itest_scene)?, variables, &[]),
+        ));
+        files.push((
+            "run_itest.sh".to_string(),
+            render(
+                require_template("itest_script", &templates.itest_script)?,
+                variables,
+                &[("build_steps", &build_steps)],
+            ),
+        ));
+    }
+
+    // Workspace members build into the workspace target dir, the .gdextension paths expect the configured one
+    if in_workspace || options.has_custom_target_dir() {
+        files.push((
+            format!("{}/.cargo/config.toml", crate_dir),
+            format!("[build]\ntarget-dir = {}\n", json_string(options.target_dir())),
+        ));
+    }
+
+    // Write the "Rebuild Rust" editor plugin
+    if options.editor_plugin {
+        let plugin_cfg = render(require_template("editor_plugin_cfg", &templates.editor_plugin_cfg)?, variables, &[]);
+        let plugin_script = render(
+            require_template("editor_plugin_script", &templates.editor_plugin_script)?,
+            variables,
+            &[
+                ("crate_dir", &crate_dir),
+                ("cargo", &json_string(options.cargo_program())),
+                ("cargo_args", &get_plugin_cargo_args(&crate_dir, options)),
+            ],
+        );
+        files.push((format!("{}/plugin.cfg", EDITOR_PLUGIN_DIR), plugin_cfg));
+        files.push((format!("{}/plugin.gd", EDITOR_PLUGIN_DIR), plugin_script));
+    }
+
+    // Write the export plugin filling bin/ from the target dir right before Godot packs the libraries
+    if options.library_layout == LibraryLayout::Split {
+        let library_copies: Vec<String> = crates
+            .iter()
+            .flat_map(|names| options.targets.iter().map(move |target| (names, target)))
+            .filter_map(|(names, target)| {
+                Some(format!(
+                    "    [{}, \"res://{}\"],",
+                    json_string(&resource_path(&built_library_path(names, &crate_dir, options, target)?)),
+                    copied_library_path(names, target)?
+                ))
+            })
+            .collect();
+        let plugin_cfg = render(require_template("export_plugin_cfg", &templates.export_plugin_cfg)?, variables, &[]);
+        let plugin_script = render(require_template("export_plugin_script", &templates.export_plugin_script)?, variables, &[]);
+        let export_hook = render(
+            require_template("export_plugin_hook", &templates.export_plugin_hook)?,
+            variables,
+            &[("library_copies", &library_copies.join("\n"))],
+        );
+        files.push((format!("{}/plugin.cfg", EXPORT_PLUGIN_DIR), plugin_cfg));
+        files.push((format!("{}/plugin.gd", EXPORT_PLUGIN_DIR), plugin_script));
+        files.push((format!("{}/export_plugin.gd", EXPORT_PLUGIN_DIR), export_hook));
+    }
+
+    // Write the project README last so its layout section lists every generated file
+    if options.readme {
+        let mut readme = require_template("readme", &templates.readme)?.to_string();
+        if !registry_entries.is_empty() {
+            readme.push('\n');
+            readme.push_str(require_template("readme_class_registry", &templates.readme_class_registry)?);
+        }
+        let mut paths: Vec<&str> = files.iter().map(|(path, _)| path.as_str()).collect();
+        paths.extend(["README.md", METADATA_FILE]);
+        // A crate outside the project is listed next to it instead of under a ".." folder
+        let (root, paths): (&str, Vec<String>) = match options.crate_location {
+            CrateLocation::Inside => (project_name, paths.iter().map(|path| path.to_string()).collect()),
+            CrateLocation::Sibling => (
+                ".",
+                paths
+                    .iter()
+                    .map(|path| path.strip_prefix("../").map_or_else(|| format!("{}/{}", project_name, path), str::to_string))
+                    .collect(),
+            ),
+            CrateLocation::Root => (
+                project_name,
+                paths
+                    .iter()
+                    .map(|path| {
+                        path.strip_prefix("../")
+                            .map_or_else(|| format!("{}/{}", ROOT_LAYOUT_GODOT_DIR, path), str::to_string)
+                    })
+                    .collect(),
+            ),
+        };
+        let paths: Vec<&str> = paths.iter().map(String::as_str).collect();
+        let layout: Vec<String> = format_file_tree(root, &paths).into_iter().map(|(line, _)| line).collect();
+        let readme = render(
+            &readme,
+            variables,
+            &[
+                ("layout", &layout.join("\n")),
+                ("build_steps", &get_readme_build_steps(names, &crate_dir, options)),
+                ("gdextension", &gdextension_relative_path(project_name, options.flavor)),
+                ("crate_dir", &crate_dir),
+            ],
+        );
+        files.push(("README.md".to_string(), readme));
+    }
+
+    if options.existing_project == ExistingProject::Retrofit {
+        files.retain(|(path, _)| !GODOT_PROJECT_FILES.contains(&path.as_str()));
+    }
+
+    // Write the gen_gdext metadata file holding the per-file update policies
+    let mut metadata = ProjectMetadata::for_new_project(&crate_dir);
+    metadata.env = options.env.clone();
+    metadata.cargo = options.cargo.clone();
+    metadata.cargo_args = options.cargo_args.clone();
+    metadata.godot = options.godot.clone();
+    metadata.editor = options.editor.clone();
+    metadata.target_dir = options.target_dir.trim().to_string();
+    metadata.extension_crates = options.extension_crates.clone();
+    metadata.display_name = options.display_name.trim().to_string();
+    metadata.class_prefix = options.class_prefix.trim().to_string();
+    metadata.author = options.author.trim().to_string();
+    metadata.template_variables = options.template_variables.clone();
+    for path in class_modules {
+        metadata.file_policies.insert(path, FilePolicy::Never);
+    }
+    for crate_name in &options.extension_crates {
+        metadata
+            .file_policies
+            .insert(format!("{}/{}/src/lib.rs", crate_dir, crate_name), FilePolicy::Never);
+    }
+    // Both are line lists users add to, later runs only append what's missing
+    metadata.file_policies.insert(format!("{}.gitignore", repository), FilePolicy::Merge);
+    metadata.file_policies.insert(format!("{}.gitattributes", repository), FilePolicy::Merge);
+    if options.readme {
+        metadata.file_policies.insert("README.md".to_string(), FilePolicy::Never);
+    }
+    if options.license != License::None {
+        metadata.file_policies.insert("LICENSE".to_string(), FilePolicy::Never);
+    }
+    for (_, path, _) in class_icons {
+        metadata.file_policies.insert(path, FilePolicy::Never);
+    }
+    if options.itest {
+        metadata.file_policies.insert(format!("{}/src/itest.rs", crate_dir), FilePolicy::Never);
+    }
+    if options.code_style {
+        metadata.file_policies.insert(format!("{}/rustfmt.toml", crate_dir), FilePolicy::Never);
+        metadata.file_policies.insert(format!("{}/clippy.toml", crate_dir), FilePolicy::Never);
+    }
+    if options.prelude_module {
+        metadata.file_policies.insert(format!("{}/src/prelude.rs", crate_dir), FilePolicy::Never);
+    }
+    if !registry_entries.is_empty() {
+        metadata
+            .file_policies
+            .insert(format!("{}/{}", crate_dir, CLASS_REGISTRY_FILE), FilePolicy::Merge);
+    }
+    files.push((METADATA_FILE.to_string(), metadata.to_yaml()));
+
+    Ok(files)
+}
+
+// One `<copy> "<built library>" "<bin path>"` line per target filled into a copy script template
+fn get_copy_script_content(
+    template: &str,
+    variables: &BTreeMap<String, String>,
+    copy: &str,
+    crates: &[ProjectNames],
+    crate_dir: &str,
+    options: &ProjectOptions,
+) -> String {
+    let copy_commands: Vec<String> = crates
+        .iter()
+        .flat_map(|names| options.targets.iter().map(move |target| (names, target)))
+        .filter_map(|(names, target)| {
+            Some(format!(
+                "{} \"{}\" \"{}\"",
+                copy,
+                built_library_path(names, crate_dir, options, target)?,
+                copied_library_path(names, target)?
+            ))
+        })
+        .collect();
+    render(template, variables, &[("copy_commands", &copy_commands.join("\n"))])
+}
+
+// Matrix entries of the build workflow, each target builds on a runner of its own platform
+fn get_workflow_matrix(names: &ProjectNames, options: &ProjectOptions) -> String {
+    let entries: Vec<String> = options
+        .targets
+        .iter()
+        .filter_map(|target| {
+            let (profile, file) = (options.target_profile(target)?, library_file(names, target)?);
+            let os = match target.split('.').next()? {
+                "windows" => "windows-latest",
+                "macos" => "macos-latest",
+                _ => "ubuntu-latest",
+            };
+            let mut cargo_args = profile_args(&profile);
+            cargo_args.extend(options.cargo_args.iter().cloned());
+            let cargo_args = cargo_args.join(" ");
+            Some(format!(
+                "          - target: {}\n            os: {}\n            cargo_args: \"{}\"\n            dir: {}/{}\n            file: {}",
+                target,
+                os,
+                cargo_args,
+                options.target_dir(),
+                profile,
+                file
+            ))
+        })
+        .collect();
+    entries.join("\n")
+}
+
+// One build-<platform>-<profile> recipe per target, `indent` is what the runner expects in front of commands
+fn get_build_recipes(names: &ProjectNames, crate_dir: &str, options: &ProjectOptions, indent: &str) -> (Vec<String>, String) {
+    let mut recipe_names = Vec::new();
+    let mut recipes = String::new();
+    for target in &options.targets {
+        let Some(profile) = options.target_profile(target).filter(|_| library_file(names, target).is_some()) else {
+            continue;
+        };
+        let platform = target.split('.').next().unwrap_or_default();
+        let name = format!("build-{}-{}", platform, profile);
+        recipes.push_str(&format!("# Build the {} library\n{}:\n", target, name));
+        recipes.push_str(&format!("{}{}\n", indent, options.cargo_build_command(crate_dir, &profile)));
+        if options.library_layout == LibraryLayout::Bin {
+            let copy = if platform == "windows" {
+                "powershell -File copy_libs.ps1"
+            } else {
+                "sh copy_libs.sh"
+            };
+            recipes.push_str(&format!("{}{}\n", indent, copy));
+        }
+        recipes.push('\n');
+        recipe_names.push(name);
+    }
+    if options.itest {
+        recipes.push_str(&format!("# Run the integration tests in a headless Godot\nitest:\n{}sh run_itest.sh\n", indent));
+        recipe_names.push("itest".to_string());
+    }
+    (recipe_names, recipes.trim_end().to_string())
+}
+
+fn json_string(value: &str) -> String {
+    serde_json::to_string(value).unwrap_or_default()
 }
 
-pub fn get_lib_content(templates: &ProjectTemplates, project_name: &str) -> String {
-    templates.lib_content.replace("{project_name}", &convert_to_camel_case(project_name))
+// The editor plugin's extra cargo arguments, Godot's working directory is unrelated to the project so the target dir is made absolute
+fn get_plugin_cargo_args(crate_dir: &str, options: &ProjectOptions) -> String {
+    let mut args = String::new();
+    if options.has_custom_target_dir() {
+        let target_dir = resource_path(&target_dir_path(crate_dir, options.target_dir()));
+        args.push_str(&format!(", \"--target-dir\", ProjectSettings.globalize_path({})", json_string(&target_dir)));
+    }
+    for arg in &options.cargo_args {
+        args.push_str(&format!(", {}", json_string(arg)));
+    }
+    args
 }
 
-pub fn get_gdextension_content(templates: &ProjectTemplates, project_name: &str, godot_version: &str, reloadable: bool, targets: &[String]) -> String {
-    let mut content = templates
-        .gdextension
-        .replace("{project_name}", project_name)
-        .replace("compatibility_minimum = 4.2", &format!("compatibility_minimum = {}", godot_version))
-        .replace("reloadable = true", &format!("reloadable = {}", if reloadable { "true" } else { "false" }));
+// The tasks.json entries, a cargo build per target plus the copy into bin/ when the project loads from there
+fn get_vscode_tasks(names: &ProjectNames, crate_dir: &str, options: &ProjectOptions) -> String {
+    let mut tasks: Vec<String> = options
+        .targets
+        .iter()
+        .filter_map(|target| {
+            let profile = options.target_profile(target).filter(|_| library_file(names, target).is_some())?;
+            let mut args = vec!["build".to_string(), "--manifest-path".to_string(), format!("{}/Cargo.toml", crate_dir)];
+            args.extend(profile_args(&profile));
+            if options.has_custom_target_dir() {
+                args.extend(["--target-dir".to_string(), target_dir_path(crate_dir, options.target_dir())]);
+            }
+            args.extend(options.cargo_args.iter().cloned());
+            Some(format!(
+                "        {{\n            \"label\": \"build {}\",\n            \"type\": \"shell\",\n            \"command\": {},\n            \"args\": [{}],\n            \"problemMatcher\": [\"$rustc\"],\n            \"group\": \"build\"\n        }}",
+                target,
+                json_string(options.cargo_program()),
+                args.iter().map(|arg| json_string(arg)).collect::<Vec<_>>().join(", ")
+            ))
+        })
+        .collect();
+    if options.library_layout == LibraryLayout::Bin {
+        tasks.push(
+            "        {\n            \"label\": \"copy libraries to bin\",\n            \"type\": \"shell\",\n            \"command\": \"sh copy_libs.sh\",\n            \"windows\": { \"command\": \"powershell -File copy_libs.ps1\" }\n        }"
+                .to_string(),
+        );
+    }
+    tasks.join(",\n")
+}
 
-    let target_lines: Vec<String> = targets
+// One list item per selected target, cargo only builds for the platform it runs on
+fn get_readme_build_steps(names: &ProjectNames, crate_dir: &str, options: &ProjectOptions) -> String {
+    let steps: Vec<String> = options
+        .targets
         .iter()
         .filter_map(|target| {
-            let library_path = match target.as_str() {
-                "linux.debug.x86_64" => format!("res://rust/target/debug/lib{}.so", project_name),
-                "linux.release.x86_64" => format!("res://rust/target/release/lib{}.so", project_name),
-                "windows.debug.x86_64" => format!("res://rust/target/debug/{}.dll", project_name),
-                "windows.release.x86_64" => format!("res://rust/target/release/{}.dll", project_name),
-                "macos.debug" => format!("res://rust/target/debug/lib{}.dylib", project_name),
-                "macos.release" => format!("res://rust/target/release/lib{}.dylib", project_name),
-                _ => return None,
-            };
-            Some(format!("{} = \"{}\"", target, library_path))
+            let command = options.cargo_build_command(crate_dir, &options.target_profile(target)?);
+            let mut step = format!(
+                "- `{}`: `{}` builds `{}`",
+                target,
+                command,
+                built_library_path(names, crate_dir, options, target)?
+            );
+            if options.library_layout == LibraryLayout::Bin {
+                let script = if target.starts_with("windows.") {
+                    "powershell -File copy_libs.ps1"
+                } else {
+                    "sh copy_libs.sh"
+                };
+                step.push_str(&format!(", `{}` copies it to `{}`", script, copied_library_path(names, target)?));
+            }
+            Some(step)
         })
         .collect();
+    if steps.is_empty() {
+        return "- No targets were selected, add library paths to the `.gdextension` file's [libraries] section.".to_string();
+    }
+    steps.join("\n")
+}
 
-    if !target_lines.is_empty() {
-        content.push_str(&format!("[libraries]\n{}\n", target_lines.join("\n")));
-    } else {
-        content.push_str("[libraries]\n"); // Optional: empty libraries section
+pub fn write_project_files(writer: &ProjectWriter, files: &[(String, String)]) -> Result<(), CreateError> {
+    for (relative_path, content) in files {
+        if let Some(parent) = Path::new(relative_path).parent() {
+            writer.create_dir(&parent.to_string_lossy())?;
+        }
+        writer.write(relative_path, content)?;
+    }
+    Ok(())
+}
+
+// Indented tree lines for a list of relative paths, files carry their index into `paths`
+pub fn format_file_tree(root: &str, paths: &[&str]) -> Vec<(String, Option<usize>)> {
+    let mut order: Vec<usize> = (0..paths.len()).collect();
+    order.sort_by_key(|&index| paths[index]);
+
+    let mut lines = vec![(format!("{}/", root), None)];
+    let mut open_dirs: Vec<&str> = Vec::new();
+    for index in order {
+        let mut components: Vec<&str> = paths[index].split('/').collect();
+        let file_name = components.pop().unwrap_or_default();
+
+        let shared = open_dirs.iter().zip(&components).take_while(|(open, dir)| open == dir).count();
+        open_dirs.truncate(shared);
+        for dir in &components[shared..] {
+            lines.push((format!("{}{}/", "  ".repeat(open_dirs.len() + 1), dir), None));
+            open_dirs.push(dir);
+        }
+        lines.push((format!("{}{}", "  ".repeat(open_dirs.len() + 1), file_name), Some(index)));
+    }
+    lines
+}
+
+// Adds the declaration after the last `mod` line, or at the top when the file has none
+fn insert_mod_declaration(content: &str, declaration: &str) -> String {
+    let mut offset = 0;
+    let mut insert_at = None;
+    for line in content.split_inclusive('\n') {
+        offset += line.len();
+        let trimmed = line.trim_start();
+        if (trimmed.starts_with("mod ") || trimmed.starts_with("pub mod ")) && trimmed.trim_end().ends_with(';') {
+            insert_at = Some(offset);
+        }
     }
 
+    let mut content = content.to_string();
+    match insert_at {
+        Some(insert_at) => content.insert_str(insert_at, declaration),
+        None => content.insert_str(0, &format!("{}\n", declaration)),
+    }
     content
 }
 
-pub fn create_project(
-    project_name: &str,
-    log: Arc<Mutex<String>>, // Change to Arc<Mutex<String>>
-    templates: &ProjectTemplates,
-    godot_version: &str,
-    reloadable: bool,
-    targets: &[String],
-    precompile_lib: bool,
-) -> Result<(), String> {
-    let mut log_content = String::new();
-    log_content.push_str(&format!("Creating project '{}'\n", project_name));
+// The compatibility_minimum a .gdextension was written with, the {godot_version} of the project
+fn gdextension_godot_version(path: &Path) -> Option<GodotVersion> {
+    let content = fs::read_to_string(path).ok()?;
+    let gdextension = GdExtensionFile::parse(&content).ok()?;
+    unquote(gdextension.get("configuration", "compatibility_minimum")?).parse().ok()
+}
 
-    // Create Godot project directory
-    let godot_dir = project_name.to_string();
-    fs::create_dir_all(&godot_dir).expect("Failed to create Godot project directory");
+fn add_gdextension_icon(path: &Path, gdextension: &str, class_name: &str, icon: &str) -> Result<String, CreateError> {
+    let mut gdextension = GdExtensionFile::parse(gdextension).map_err(|message| CreateError::InvalidGdExtension {
+        path: path.to_path_buf(),
+        message,
+    })?;
+    gdextension.set("icons", class_name, quote(icon));
+    Ok(gdextension.to_string())
+}
+
+pub fn add_class_to_project(project_name: &str, templates: &ProjectTemplates, class: &ClassSpec, icon: &str, log: &Arc<Mutex<Log>>) -> Result<(), CreateError> {
+    let project_dir = find_project_dir(project_name);
+    let crate_dir = find_crate_dir(project_name);
+    let lib_path = project_dir.join(&crate_dir).join("src/lib.rs");
+    if !lib_path.exists() {
+        return Err(CreateError::MissingLibrary);
+    }
+
+    // Follows the layout the project was created with: with a class registry into its domain folder, falling back to classes/,
+    // otherwise a module next to lib.rs
+    let module = class_module_name(&class.name)?;
+    let src_dir = format!("{}/src", crate_dir);
+    let registry_path = project_dir.join(&crate_dir).join(CLASS_REGISTRY_FILE);
+    let module_dir = registry_path.exists().then(|| {
+        [class.base_class.domain(), "classes"]
+            .into_iter()
+            .find(|dir| project_dir.join(&src_dir).join(dir).is_dir())
+            .unwrap_or("classes")
+    });
+    let (module_path, declaring_path) = match module_dir {
+        Some(dir) => (format!("{}/{}/{}.rs", src_dir, dir, module), registry_path),
+        None => (format!("{}/{}.rs", src_dir, module), lib_path),
+    };
+    if project_dir.join(&module_path).exists() {
+        return Err(CreateError::DuplicateClass(class.name.clone()));
+    }
+    let declaring_content = fs::read_to_string(&declaring_path).map_err(|source| CreateError::WriteFile {
+        path: declaring_path.clone(),
+        source,
+    })?;
+    let declared = match module_dir {
+        Some(dir) => merge_lines(&declaring_content, &class_registry_entry(dir, &module)),
+        None => insert_mod_declaration(&declaring_content, &format!("mod {};\n", module)),
+    };
+
+    // The same variables as at creation: names and values from the metadata file, the crate name from Cargo.toml
+    // and the Godot version from the .gdextension
+    let mut options = ProjectMetadata::load(&project_dir)?.saved_options();
+    options.crate_name = ProjectNames::load(project_name).crate_name;
+    if let Some(version) = gdextension_godot_version(&find_gdextension(project_name)) {
+        options.godot_version = version;
+    }
+    let variables = template_variables(project_name, &ProjectNames::new(project_name, &options), &options);
+
+    // Existing files are edited in place regardless of their update policy, the writer only provides the rollback
+    let confirm_overwrite = |_: &str| false;
+    let writer = ProjectWriter::new(&project_dir, log, ExistingProject::Update, &confirm_overwrite)?;
+    let use_prelude = project_dir.join(&src_dir).join("prelude.rs").exists();
+    let added = match module_dir {
+        Some(dir) => writer.create_dir(&format!("{}/{}", src_dir, dir)),
+        None => Ok(()),
+    };
+    let added = added
+        .and_then(|()| get_class_module_content(templates, class, use_prelude, &variables))
+        .and_then(|content| writer.write(&module_path, &content))
+        .and_then(|_| writer.update_file(&declaring_path, &declared))
+        .and_then(|()| {
+            if icon.trim().is_empty() {
+                return Ok(());
+            }
+            let gdextension_path = find_gdextension(project_name);
+            let gdextension = fs::read_to_string(&gdextension_path).map_err(|source| CreateError::WriteFile {
+                path: gdextension_path.clone(),
+                source,
+            })?;
+            writer.update_file(
+                &gdextension_path,
+                &add_gdextension_icon(&gdextension_path, &gdextension, &class.name, icon.trim())?,
+            )
+        });
+    if let Err(err) = added {
+        writer.rollback();
+        return Err(err);
+    }
+
+    let mut log_inner = log.lock().unwrap();
+    log_inner.info(&format!("Added class '{}' to {}.", class.name, module_path));
+    Ok(())
+}
+
+// Commonly used engine classes, a GDExtension class with one of these names fails to register
+const GODOT_CLASSES: &[&str] = &[
+    "AcceptDialog",
+    "AnimatedSprite2D",
+    "AnimatedSprite3D",
+    "AnimationPlayer",
+    "AnimationTree",
+    "Area2D",
+    "Area3D",
+    "AStar2D",
+    "AStar3D",
+    "AStarGrid2D",
+    "AudioListener2D",
+    "AudioListener3D",
+    "AudioServer",
+    "AudioStream",
+    "AudioStreamPlayer",
+    "AudioStreamPlayer2D",
+    "AudioStreamPlayer3D",
+    "BackBufferCopy",
+    "BaseButton",
+    "BoneAttachment3D",
+    "BoxContainer",
+    "BoxMesh",
+    "BoxShape3D",
+    "Button",
+    "Camera2D",
+    "Camera3D",
+    "CanvasItem",
+    "CanvasLayer",
+    "CanvasModulate",
+    "CapsuleShape2D",
+    "CapsuleShape3D",
+    "CenterContainer",
+    "CharacterBody2D",
+    "CharacterBody3D",
+    "CheckBox",
+    "CheckButton",
+    "CircleShape2D",
+    "ClassDB",
+    "CollisionObject2D",
+    "CollisionObject3D",
+    "CollisionPolygon2D",
+    "CollisionPolygon3D",
+    "CollisionShape2D",
+    "CollisionShape3D",
+    "ColorPicker",
+    "ColorRect",
+    "ConfirmationDialog",
+    "Container",
+    "Control",
+    "CPUParticles2D",
+    "CPUParticles3D",
+    "Curve",
+    "Curve2D",
+    "Curve3D",
+    "DirAccess",
+    "DirectionalLight2D",
+    "DirectionalLight3D",
+    "DisplayServer",
+    "EditorInterface",
+    "EditorPlugin",
+    "EditorScript",
+    "Engine",
+    "Environment",
+    "FileAccess",
+    "FileDialog",
+    "FlowContainer",
+    "Font",
+    "GDExtension",
+    "GDScript",
+    "GPUParticles2D",
+    "GPUParticles3D",
+    "Geometry2D",
+    "Geometry3D",
+    "GridContainer",
+    "GridMap",
+    "HBoxContainer",
+    "HSlider",
+    "HTTPClient",
+    "HTTPRequest",
+    "Image",
+    "ImageTexture",
+    "Input",
+    "InputEvent",
+    "InputEventKey",
+    "InputEventMouse",
+    "InputMap",
+    "ItemList",
+    "JavaScriptBridge",
+    "JSON",
+    "Joint2D",
+    "Joint3D",
+    "Label",
+    "Label3D",
+    "Light2D",
+    "Light3D",
+    "Line2D",
+    "LineEdit",
+    "LinkButton",
+    "MarginContainer",
+    "Marker2D",
+    "Marker3D",
+    "Material",
+    "MenuButton",
+    "Mesh",
+    "MeshInstance2D",
+    "MeshInstance3D",
+    "MultiplayerAPI",
+    "MultiplayerSpawner",
+    "MultiplayerSynchronizer",
+    "NavigationAgent2D",
+    "NavigationAgent3D",
+    "NavigationRegion2D",
+    "NavigationRegion3D",
+    "NinePatchRect",
+    "Node",
+    "Node2D",
+    "Node3D",
+    "Object",
+    "OmniLight3D",
+    "OptionButton",
+    "OS",
+    "PackedScene",
+    "Panel",
+    "PanelContainer",
+    "ParallaxBackground",
+    "ParallaxLayer",
+    "Path2D",
+    "Path3D",
+    "PathFollow2D",
+    "PathFollow3D",
+    "PhysicalBone3D",
+    "PhysicsBody2D",
+    "PhysicsBody3D",
+    "PhysicsServer2D",
+    "PhysicsServer3D",
+    "PointLight2D",
+    "Polygon2D",
+    "Popup",
+    "PopupMenu",
+    "ProgressBar",
+    "ProjectSettings",
+    "RandomNumberGenerator",
+    "Range",
+    "RayCast2D",
+    "RayCast3D",
+    "RectangleShape2D",
+    "RefCounted",
+    "RemoteTransform2D",
+    "RemoteTransform3D",
+    "RenderingServer",
+    "Resource",
+    "ResourceLoader",
+    "ResourceSaver",
+    "RichTextLabel",
+    "RigidBody2D",
+    "RigidBody3D",
+    "SceneTree",
+    "ScrollContainer",
+    "Shader",
+    "ShaderMaterial",
+    "Shape2D",
+    "Shape3D",
+    "Skeleton2D",
+    "Skeleton3D",
+    "Slider",
+    "SphereMesh",
+    "SphereShape3D",
+    "SpinBox",
+    "SplitContainer",
+    "SpotLight3D",
+    "SpringArm3D",
+    "Sprite2D",
+    "Sprite3D",
+    "StaticBody2D",
+    "StaticBody3D",
+    "StyleBox",
+    "SubViewport",
+    "SubViewportContainer",
+    "TabBar",
+    "TabContainer",
+    "TextEdit",
+    "Texture",
+    "Texture2D",
+    "TextureButton",
+    "TextureRect",
+    "Theme",
+    "Thread",
+    "TileMap",
+    "TileMapLayer",
+    "TileSet",
+    "Time",
+    "Timer",
+    "TouchScreenButton",
+    "Tree",
+    "Tween",
+    "VBoxContainer",
+    "VehicleBody3D",
+    "VehicleWheel3D",
+    "VideoStreamPlayer",
+    "Viewport",
+    "VisibleOnScreenNotifier2D",
+    "VisibleOnScreenNotifier3D",
+    "VSlider",
+    "Window",
+    "WorldEnvironment",
+];
+
+pub fn name_collisions(names: &ProjectNames, options: &ProjectOptions, cancel: &AtomicBool) -> Vec<String> {
+    let mut warnings = Vec::new();
+
+    let class_names = std::iter::once(main_class_name(names)).chain(options.classes.iter().map(|class| class.name.clone()));
+    for class_name in class_names.filter(|name| GODOT_CLASSES.contains(&name.as_str())) {
+        warnings.push(format!("Class '{}' has the same name as a built-in Godot class.", class_name));
+    }
 
-    // Create project.godot file
-    let project_godot_content = format!("[gd_project]\nversion=4.0\nrun/main_scene=\"res://main.tscn\"\n");
-    fs::write(format!("{}/project.godot", godot_dir), project_godot_content).expect("Failed to create project.godot file");
+    // Being offline is not worth interrupting creation for, so a failed search counts as no match
+    if crate_exists_on_crates_io(options, &names.crate_name, cancel).unwrap_or(false) {
+        warnings.push(format!("A crate named '{}' already exists on crates.io.", names.crate_name));
+    }
 
-    // Create Rust folder inside the Godot project directory
-    let rust_dir = format!("{}/rust", godot_dir);
-    fs::create_dir_all(&rust_dir).expect("Failed to create Rust directory");
+    warnings
+}
 
-    // Write the Cargo.toml file
-    let cargo_toml_content = templates.cargo_toml.replace("{project_name}", project_name);
-    fs::write(format!("{}/Cargo.toml", rust_dir), cargo_toml_content).expect("Failed to create Cargo.toml file");
+// Long enough for a slow registry, short enough not to hold up creation on a machine that can't reach it
+const CRATES_IO_TIMEOUT: Duration = Duration::from_secs(15);
 
-    // Write the .gitignore file
-    let gitignore_content = get_gitignore_content(templates);
-    fs::write(format!("{}/.gitignore", rust_dir), gitignore_content).expect("Failed to create .gitignore file");
+fn crate_exists_on_crates_io(options: &ProjectOptions, crate_name: &str, cancel: &AtomicBool) -> std::io::Result<bool> {
+    let mut child = Command::new(options.cargo_program())
+        .envs(&options.env)
+        .args(["search", "--limit", "5", crate_name])
+        .stdin(Stdio::null())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::null())
+        .spawn()?;
+    // The five result lines fit in the pipe, so it is only read once cargo has exited
+    let started = Instant::now();
+    while child.try_wait()?.is_none() {
+        if cancel.load(Ordering::Relaxed) || started.elapsed() > CRATES_IO_TIMEOUT {
+            let _ = child.kill();
+            let _ = child.wait();
+            return Err(std::io::Error::from(ErrorKind::TimedOut));
+        }
+        thread::sleep(Duration::from_millis(100));
+    }
+    let mut stdout = String::new();
+    if let Some(mut pipe) = child.stdout.take() {
+        pipe.read_to_string(&mut stdout)?;
+    }
+    // crates.io treats '-' and '_' as the same character in crate names
+    let normalized = crate_name.replace('-', "_");
+    Ok(stdout
+        .lines()
+        .filter_map(|line| line.split(" = ").next())
+        .any(|name| name.replace('-', "_") == normalized))
+}
 
-    // Create Rust source directory
-    let rust_src_dir = format!("{}/src", rust_dir);
-    fs::create_dir_all(&rust_src_dir).expect("Failed to create Rust source directory");
+// The problems with the project, crate and class names alone, cheap enough to check on every keystroke
+pub fn name_problems(project_name: &str, options: &ProjectOptions) -> Vec<String> {
+    if project_name.is_empty() {
+        return vec![CreateError::EmptyName.to_string()];
+    }
+    if !is_valid_project_name(project_name) {
+        return vec![CreateError::InvalidProjectName(project_name.to_string()).to_string()];
+    }
 
-    // Write lib.rs file
-    let lib_content = get_lib_content(templates, project_name);
-    fs::write(format!("{}/lib.rs", rust_src_dir), lib_content).expect("Failed to create lib.rs file");
+    let names = ProjectNames::new(project_name, options);
+    let mut problems = Vec::new();
+    if let Some(reason) = crate_name_problem(&names.crate_name) {
+        problems.push(CreateError::InvalidCrateName(names.crate_name.clone(), reason).to_string());
+    }
+    if !is_valid_class_prefix(&names.class_prefix) {
+        problems.push(CreateError::InvalidClassPrefix(names.class_prefix).to_string());
+    }
+    problems
+}
 
-    // Write .gdextension file
-    let gdextension_content = get_gdextension_content(templates, project_name, godot_version, reloadable, targets);
-    fs::write(format!("{}/{}.gdextension", godot_dir, project_name), gdextension_content).expect("Failed to create .gdextension file");
+pub fn validate_settings(project_name: &str, templates: Option<&ProjectTemplates>, options: &ProjectOptions) -> Vec<String> {
+    let mut problems = name_problems(project_name, options);
 
+    if problems.is_empty()
+        && (Path::new(project_name).exists() || sibling_crate_exists(project_name, options))
+        && options.existing_project == ExistingProject::Refuse
     {
-        let mut log_inner = log.lock().unwrap();
-        log_inner.push_str(&format!("Created Godot project '{}' with Rust integration.\n", project_name));
+        problems.push(CreateError::AlreadyExists.to_string());
+    }
+    if options.existing_project == ExistingProject::Retrofit {
+        if !Path::new(project_name).join("project.godot").is_file() {
+            problems.push(format!("Adding Rust needs an existing Godot project, {} has no project.godot.", project_name));
+        }
+        if options.crate_location == CrateLocation::Root {
+            problems.push("The root layout moves the Godot project into godot/, pick another crate location to add Rust to a project.".to_string());
+        }
+    }
+
+    // The project is created in the working directory, or written into when it already exists
+    let output_dir = if Path::new(project_name).is_dir() {
+        Path::new(project_name)
+    } else {
+        Path::new(".")
+    };
+    if fs::metadata(output_dir).is_ok_and(|metadata| metadata.permissions().readonly()) {
+        problems.push(format!("{} is not writable.", output_dir.display()));
+    }
+
+    if options.targets.is_empty() {
+        problems.push("Select at least one target.".to_string());
+    }
+    problems.extend(options.release_profile.problems());
+    for (target, profile) in &options.target_profiles {
+        let profile = profile.trim();
+        if !profile.is_empty() && !is_valid_profile_name(profile) {
+            problems.push(format!(
+                "'{}' is not a valid cargo profile for {}, use letters, digits, '-' and '_' and start with a letter.",
+                profile, target
+            ));
+        }
+    }
+    if !options.locale.trim().is_empty() && !is_valid_locale(options.locale.trim()) {
+        problems.push(format!(
+            "'{}' is not a locale, expected a language code like de or pt_BR.",
+            options.locale.trim()
+        ));
+    }
+    if !is_valid_entry_symbol(options.entry_symbol()) {
+        problems.push(format!(
+            "'{}' is not a valid entry symbol, use letters, digits and '_' and don't start with a digit.",
+            options.entry_symbol()
+        ));
+    }
+    if let Some(maximum) = options.compatibility_maximum.filter(|&maximum| options.godot_version.exceeds(maximum)) {
+        problems.push(format!(
+            "The maximum Godot version {} is older than the minimum {}.",
+            maximum, options.godot_version
+        ));
+    }
+    if options.target_dir.contains(['"', '\n']) {
+        problems.push("The cargo target dir can't contain quotes or line breaks.".to_string());
+    }
+    if options.github_workflow && Path::new(options.target_dir()).is_absolute() {
+        problems.push("The GitHub workflow builds on fresh runners, it can't use an absolute cargo target dir.".to_string());
+    }
+    for (target, path) in &options.library_paths {
+        if path.contains(['"', '\n']) {
+            problems.push(format!("The library path for {} can't contain quotes or line breaks.", target));
+        }
+    }
+    for target in options.targets.iter().filter(|target| !KNOWN_TARGETS.contains(&target.as_str())) {
+        problems.push(format!("Unknown target '{}'.", target));
+    }
+
+    if options.github_workflow && options.crate_location == CrateLocation::Sibling {
+        problems.push("The GitHub workflow builds the crate from the project's repository, it can't reach a crate next to the project.".to_string());
+    }
+
+    for name in options.template_variables.keys() {
+        if !is_valid_variable_name(name) {
+            problems.push(format!("The template variable '{}' can only use lowercase letters, digits and '_'.", name));
+        } else if BUILTIN_VARIABLES.contains(&name.as_str()) {
+            problems.push(format!("The template variable '{}' is built in and can't be redefined.", name));
+        }
+    }
+
+    // Extension crates sit in folders of the main crate and build into its target dir, so none of those names may collide
+    let mut library_names = vec![ProjectNames::new(project_name, options).library_name()];
+    for crate_name in &options.extension_crates {
+        let library_name = ProjectNames::for_extension_crate(crate_name).library_name();
+        if let Some(reason) = crate_name_problem(crate_name) {
+            problems.push(CreateError::InvalidCrateName(crate_name.clone(), reason).to_string());
+        } else if library_names.contains(&library_name) || crate_name == project_name || ["src", options.target_dir()].contains(&crate_name.as_str()) {
+            problems.push(format!(
+                "The extension crate '{}' clashes with another crate, a .gdextension or a folder of the main crate.",
+                crate_name
+            ));
+        }
+        library_names.push(library_name);
+    }
+    if !options.extension_crates.is_empty() {
+        if options.github_workflow {
+            problems.push("The GitHub workflow uploads one library per target, it doesn't cover extension crates.".to_string());
+        }
+        let crate_dir = crate_relative_dir(project_name, options.crate_location);
+        if find_cargo_workspace(&project_dir(project_name, options.crate_location), &crate_dir).is_some() {
+            problems.push("Extension crates make the crate a Cargo workspace of its own, which can't be nested in the enclosing one.".to_string());
+        }
     }
 
-    if precompile_lib {
-        let log_clone = Arc::clone(&log);
-        let project_name = project_name.to_string();
-        let are_targets_empty = targets.is_empty();
+    for feature in options
+        .godot_features
+        .iter()
+        .filter(|feature| !KNOWN_GODOT_FEATURES.contains(&feature.as_str()))
+    {
+        problems.push(format!("Unknown godot crate feature '{}'.", feature));
+    }
+    for dependency in options
+        .dependencies
+        .iter()
+        .filter(|dependency| !KNOWN_DEPENDENCIES.iter().any(|(name, _, _)| name == dependency))
+    {
+        problems.push(format!("Unknown dependency '{}'.", dependency));
+    }
 
-        thread::spawn(move || {
-            {
-                let mut log_inner = log_clone.lock().unwrap();
-                log_inner.push_str("Compiling Rust library...\n");
+    match templates {
+        Some(templates) => {
+            problems.extend(templates.requirements.godot_range_problem(options.godot_version));
+            let lib_content = match options.lib_scaffold {
+                LibScaffold::Minimal => &templates.lib_content,
+                LibScaffold::Example => &templates.lib_content_example,
+            };
+            // A template writing #[gdextension] itself would export the default symbol the .gdextension no longer names
+            if options.entry_symbol() != DEFAULT_ENTRY_SYMBOL && !lib_content.contains("{extension_attribute}") {
+                problems.push("A custom entry symbol needs {extension_attribute} in place of #[gdextension] in the lib.rs template.".to_string());
             }
+            if let Err(err) = render_project_files(project_name, templates, options) {
+                problems.push(err.to_string());
+            }
+        }
+        None => problems.push(CreateError::TemplatesUnavailable.to_string()),
+    }
 
-            let lib_path = format!("{}/rust/src/lib.rs", project_name);
+    problems
+}
 
-            if fs::metadata(lib_path).is_ok() && !are_targets_empty {
-                let mut result = Command::new("cargo")
-                    .arg("build")
-                    .current_dir(format!("{}/rust/src", project_name))
-                    .spawn()
-                    .expect("Failed to start cargo build process");
+fn run_git(project_name: &str, subcommand: &'static str, args: &[&str], log: &Arc<Mutex<Log>>, cancel: &AtomicBool) -> Result<(), CreateError> {
+    let mut command = project_command("git", project_name)?;
+    command.arg(subcommand).args(args).current_dir(project_name);
+    match run_streamed(command, log, cancel) {
+        Ok(status) if status.success() => Ok(()),
+        Ok(_) => Err(CreateError::GitFailed(subcommand)),
+        Err(err) if err.kind() == ErrorKind::Interrupted => Err(CreateError::Cancelled),
+        Err(err) => Err(CreateError::SpawnGit(err)),
+    }
+}
 
-                if result.wait().unwrap().success() {
-                    {
-                        let mut log_inner = log_clone.lock().unwrap();
-                        log_inner.push_str("Rust library compiled successfully.\nProject created successfully.\n");
-                    }
-                } else {
-                    let mut log_inner = log_clone.lock().unwrap();
-                    log_inner.push_str("Failed to compile Rust library.\n");
-                }
-            } else {
-                let mut log_inner = log_clone.lock().unwrap();
-                log_inner.push_str("Rust library file does not exist.\n");
+// Runs git init and commits the generated files, a project that already is a repository is left alone
+fn init_git_repository(project_name: &str, log: &Arc<Mutex<Log>>, options: &ProjectOptions, cancel: &AtomicBool) -> Result<(), CreateError> {
+    if !options.git_init {
+        return Ok(());
+    }
+    if Path::new(project_name).join(".git").exists() {
+        log.lock().unwrap().info("The project already is a git repository, skipped git init.");
+        return Ok(());
+    }
+
+    let message = match options.git_commit_message.trim() {
+        "" => DEFAULT_GIT_COMMIT_MESSAGE,
+        message => message,
+    };
+    log.lock().unwrap().info("Initializing git repository...");
+    run_git(project_name, "init", &[], log, cancel)?;
+    run_git(project_name, "add", &["-A"], log, cancel)?;
+    run_git(project_name, "commit", &["-m", message], log, cancel)
+}
+
+// A failing hook is reported and the next one still runs, only cancelling stops them
+fn run_post_create_hooks(
+    project_name: &str,
+    templates: &ProjectTemplates,
+    options: &ProjectOptions,
+    log: &Arc<Mutex<Log>>,
+    cancel: &AtomicBool,
+) -> Result<(), CreateError> {
+    let template_hooks = if options.template_hooks {
+        templates.post_create_hooks.as_slice()
+    } else {
+        &[]
+    };
+    let hooks: Vec<&str> = template_hooks
+        .iter()
+        .chain(&options.post_create_hooks)
+        .map(|hook| hook.trim())
+        .filter(|hook| !hook.is_empty())
+        .collect();
+    if hooks.is_empty() {
+        return Ok(());
+    }
+
+    let dir = project_dir(project_name, options.crate_location);
+    let mut variables = template_variables(project_name, &ProjectNames::new(project_name, options), options);
+    variables.insert("crate_dir".to_string(), crate_relative_dir(project_name, options.crate_location));
+    let mut failed = 0;
+    for hook in &hooks {
+        if cancel.load(Ordering::Relaxed) {
+            return Err(CreateError::Cancelled);
+        }
+        let hook = fill_variables(hook, &variables);
+        log.lock().unwrap().info(&format!("Running {}", hook));
+        let (shell, flag) = if cfg!(windows) { ("cmd", "/C") } else { ("sh", "-c") };
+        let mut command = project_command(shell, project_name)?;
+        command.args([flag, hook.as_str()]).current_dir(&dir);
+        match run_streamed(command, log, cancel) {
+            Ok(status) if status.success() => {}
+            Ok(status) => {
+                failed += 1;
+                log.lock().unwrap().error(&format!("Post-create hook '{}' failed with {}.", hook, status));
             }
-        });
+            Err(err) if err.kind() == ErrorKind::Interrupted => return Err(CreateError::Cancelled),
+            Err(err) => {
+                failed += 1;
+                log.lock().unwrap().error(&format!("Failed to start post-create hook '{}': {}", hook, err));
+            }
+        }
+    }
+    if failed > 0 {
+        log.lock().unwrap().warn(&format!("{} of {} post-create hooks failed.", failed, hooks.len()));
+    }
+    Ok(())
+}
+
+pub fn create_project(
+    project_name: &str,
+    log: Arc<Mutex<Log>>,
+    templates: &ProjectTemplates,
+    options: &ProjectOptions,
+    cancel: &AtomicBool,
+    confirm_overwrite: &dyn Fn(&str) -> bool,
+    builds: &Mutex<Vec<TargetBuild>>,
+) -> Result<(), CreateError> {
+    log.lock().unwrap().info(&format!("Creating project '{}'", project_name));
+    let resolved;
+    let options = if options.dependencies.is_empty() {
+        options
     } else {
+        resolved = ProjectOptions {
+            dependency_versions: resolve_dependency_versions(options, &log),
+            ..options.clone()
+        };
+        &resolved
+    };
+
+    // Create Godot project directory
+    let writer = ProjectWriter::new(
+        &project_dir(project_name, options.crate_location),
+        &log,
+        options.existing_project,
+        confirm_overwrite,
+    )?;
+    let generated = writer
+        .create_dir("")
+        .and_then(|()| render_project_files(project_name, templates, options))
+        .and_then(|files| write_project_files(&writer, &files))
+        .and_then(|()| join_cargo_workspace(&writer, project_name, options));
+    if let Err(err) = generated {
+        writer.rollback();
+        return Err(err);
+    }
+
+    {
         let mut log_inner = log.lock().unwrap();
-        log_inner.push_str("Project created successfully.\n");
+        log_inner.info(&format!("Created Godot project '{}' with Rust integration.", project_name));
     }
 
-    Ok(())
+    let result = if cancel.load(Ordering::Relaxed) {
+        Err(CreateError::Cancelled)
+    } else if let Err(err) = init_git_repository(project_name, &log, options, cancel) {
+        Err(err)
+    } else if let Err(err) = run_post_create_hooks(project_name, templates, options, &log, cancel) {
+        Err(err)
+    } else if options.precompile_lib {
+        precompile_library(project_name, &log, options, cancel, builds)
+    } else {
+        Ok(())
+    };
+    if result.is_ok() {
+        log.lock().unwrap().info("Project created successfully.");
+    }
+
+    // A failed build still leaves a usable project behind, only cancelling undoes the creation
+    if let Err(CreateError::Cancelled) = result {
+        writer.rollback();
+    }
+
+    // Not being able to start the editor doesn't make the creation fail
+    if result.is_ok() && options.open_in_godot {
+        match open_in_editor(project_name) {
+            Ok(()) => log.lock().unwrap().info("Opened the project in the Godot editor."),
+            Err(err) => log.lock().unwrap().warn(&format!("Failed to open the project in Godot: {}", err)),
+        }
+    }
+    if result.is_ok() && options.open_in_editor {
+        match open_in_code_editor(project_name) {
+            Ok(()) => log.lock().unwrap().info("Opened the Rust crate in the code editor."),
+            Err(err) => log.lock().unwrap().warn(&format!("Failed to open the Rust crate: {}", err)),
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn bundled_templates() -> ProjectTemplates {
+        parse_templates(TEMPLATE_FILE, include_str!("templates.yaml")).unwrap()
+    }
+
+    // A {name} the tool fills left in rendered output means a value was never filled
+    fn assert_filled(rendered: &str) {
+        let known = BUILTIN_VARIABLES
+            .iter()
+            .chain(TEMPLATE_FIELDS.iter().flat_map(|(_, placeholders)| placeholders.iter()));
+        for name in known {
+            assert!(!rendered.contains(&format!("{{{}}}", name)), "{{{}}} is left in:\n{}", name, rendered);
+        }
+    }
+
+    #[test]
+    fn class_templates_fill_every_placeholder() {
+        let templates = bundled_templates();
+        for base_class in BaseClass::ALL {
+            for (layout, lib_scaffold) in [(CrateLayout::Flat, LibScaffold::Minimal), (CrateLayout::Classes, LibScaffold::Example)] {
+                let options = ProjectOptions {
+                    base_class,
+                    layout,
+                    lib_scaffold,
+                    classes: vec![ClassSpec {
+                        name: "Enemy".to_string(),
+                        base_class,
+                    }],
+                    ..ProjectOptions::default()
+                };
+                let names = ProjectNames::new("My Game", &options);
+                let variables = template_variables("My Game", &names, &options);
+                assert_filled(&get_lib_content(&templates, &names, &options, &variables).unwrap());
+                assert_filled(&get_class_module_content(&templates, &options.classes[0], false, &variables).unwrap());
+            }
+        }
+    }
+
+    #[test]
+    fn entry_symbol_fills_the_extension_attribute() {
+        let templates = bundled_templates();
+        let options = ProjectOptions {
+            entry_symbol: "my_init".to_string(),
+            ..ProjectOptions::default()
+        };
+        let names = ProjectNames::new("demo", &options);
+        let variables = template_variables("demo", &names, &options);
+        let lib = get_lib_content(&templates, &names, &options, &variables).unwrap();
+        assert!(lib.contains("#[gdextension(entry_symbol = my_init)]\nunsafe impl ExtensionLibrary for Demo {}"), "{}", lib);
+
+        let mut legacy = templates.clone();
+        legacy.lib_content = legacy.lib_content.replace("{extension_attribute}", "#[gdextension]");
+        assert!(validate_settings("demo", Some(&legacy), &options)
+            .iter()
+            .any(|problem| problem.contains("{extension_attribute}")));
+    }
+
+    #[test]
+    fn every_text_template_is_a_section() {
+        let templates = bundled_templates();
+        let not_text = ["requirements", "post_create_hooks", "gitignore_fragments", "licenses", "export_presets"];
+        for (field, _) in TEMPLATE_FIELDS.iter().filter(|(field, _)| !not_text.contains(field)) {
+            assert!(templates.section(field).is_some(), "{} is missing from ProjectTemplates::section", field);
+        }
+    }
+
+    #[test]
+    fn project_name_in_crate_templates_warns() {
+        let content = "lib_content: |\n  struct {project_name};\ngdextension: |\n  [configuration]\ncargo_toml: |\n  name = \"{crate_name}\"\nreadme: |\n  # {project_name}\ngitignore: |\n  target/\n";
+        let templates = parse_templates(TEMPLATE_FILE, content).unwrap();
+        let warnings = template_warnings(&templates, &BTreeMap::new());
+        assert_eq!(warnings.len(), 1, "{:?}", warnings);
+        assert!(warnings[0].starts_with("Line 2: {project_name} in lib_content"));
+    }
+
+    fn variables(pairs: &[(&str, &str)]) -> BTreeMap<String, String> {
+        pairs.iter().map(|(name, value)| (name.to_string(), value.to_string())).collect()
+    }
+
+    #[test]
+    fn fill_variables_fills_known_names_once() {
+        let variables = variables(&[("crate_name", "demo"), ("author", "{crate_name}")]);
+        assert_eq!(fill_variables("name = \"{crate_name}\" by {author}", &variables), "name = \"demo\" by {crate_name}");
+        assert_eq!(fill_variables("{unknown} {{crate_name}} fn f() {}", &variables), "{unknown} {demo} fn f() {}");
+        assert_eq!(fill_variables("{crate_name", &variables), "{crate_name");
+    }
+
+    #[test]
+    fn render_values_win_over_variables() {
+        let variables = variables(&[("class_prefix", "Game")]);
+        assert_eq!(render("struct {class_prefix};", &variables, &[("class_prefix", "Extra")]), "struct Extra;");
+        assert_eq!(render("{modules}use godot::prelude::*;", &variables, &[("modules", "mod a;\n")]), "mod a;\nuse godot::prelude::*;");
+    }
+
+    #[test]
+    fn variable_names() {
+        for name in ["year", "_private", "asset_dir2"] {
+            assert!(is_valid_variable_name(name), "{}", name);
+        }
+        for name in ["", "Year", "2d_assets", "asset-dir", "asset dir"] {
+            assert!(!is_valid_variable_name(name), "{}", name);
+        }
+    }
+
+    #[test]
+    fn replace_template_block_keeps_the_rest() {
+        let content = "# templates\ngitignore: |\n  target/\n\nlib_content: |\n  old\n";
+        assert_eq!(
+            replace_template_block(content, "gitignore", "target/\n*.so\n"),
+            "# templates\ngitignore: |\n  target/\n  *.so\n\nlib_content: |\n  old\n"
+        );
+        assert_eq!(
+            replace_template_block(content, "lib_content", "  indented\n\nend"),
+            "# templates\ngitignore: |\n  target/\n\nlib_content: |2-\n    indented\n\n  end\n"
+        );
+        assert_eq!(
+            replace_template_block("gitignore: |\n  target/", "cargo_toml", "[package]\n\n"),
+            "gitignore: |\n  target/\n\ncargo_toml: |+\n  [package]\n\n"
+        );
+    }
+
+    #[test]
+    fn add_dependencies_appends_to_the_table() {
+        let manifest = "[package]\nname = \"demo\"\n\n[dependencies]\ngodot = \"0.1\"\n\n[profile.release]\nlto = true\n";
+        let lines = ["serde = \"1\"".to_string(), "godot = \"0.2\"".to_string()];
+        assert_eq!(
+            add_dependencies(manifest, &lines),
+            "[package]\nname = \"demo\"\n\n[dependencies]\ngodot = \"0.1\"\nserde = \"1\"\n\n[profile.release]\nlto = true\n"
+        );
+        assert_eq!(
+            add_dependencies("[package]\nname = \"demo\"\n", &lines[..1]),
+            "[package]\nname = \"demo\"\n\n[dependencies]\nserde = \"1\"\n"
+        );
+        assert_eq!(add_dependencies(manifest, &[]), manifest);
+    }
+
+    fn godot_version(input: &str) -> Result<String, String> {
+        input.parse::<GodotVersion>().map(|version| version.to_string())
+    }
+
+    #[test]
+    fn godot_versions() {
+        assert_eq!(godot_version("4.2"), Ok("4.2".to_string()));
+        assert_eq!(godot_version(" v4.3.1 "), Ok("4.3.1".to_string()));
+        assert_eq!(godot_version("V4"), Ok("4.0".to_string()));
+        assert_eq!(godot_version("3.5"), Err("Godot 3.5 isn't supported, GDExtension projects need Godot 4.".to_string()));
+        for input in ["", "4.", "4.2.1.0", "4.x", "four", "-4.2"] {
+            assert!(godot_version(input).is_err(), "{}", input);
+        }
+        assert!("4.2.1".parse::<GodotVersion>().unwrap().exceeds("4.2.0".parse().unwrap()));
+        assert!(!"4.2.9".parse::<GodotVersion>().unwrap().exceeds("4.2".parse().unwrap()));
+    }
+
+    fn added(manifest: &str, member: &str) -> String {
+        match add_workspace_member(manifest, member) {
+            WorkspaceMembership::Added(content) => content,
+            other => panic!("expected {} to be added, got {:?}", member, other),
+        }
+    }
+
+    #[test]
+    fn workspace_member_single_line() {
+        assert_eq!(added("[workspace]\nmembers = [\"game\"]\n", "rust"), "[workspace]\nmembers = [\"game\", \"rust\"]\n");
+        assert_eq!(added("[workspace]\nmembers = []\n", "rust"), "[workspace]\nmembers = [\"rust\"]\n");
+        assert_eq!(
+            add_workspace_member("[workspace]\nmembers = [\"game\", \"rust\"]\n", "rust"),
+            WorkspaceMembership::Listed
+        );
+    }
+
+    #[test]
+    fn workspace_member_multi_line() {
+        let manifest = "[workspace]\nmembers = [\n    \"game\", # the game\n    \"tools\"\n]\n\n[profile.release]\nlto = true\n";
+        assert_eq!(
+            added(manifest, "rust"),
+            "[workspace]\nmembers = [\n    \"game\", # the game\n    \"tools\",\n    \"rust\",\n]\n\n[profile.release]\nlto = true\n"
+        );
+        let commented = "[workspace]\nmembers = [\n    # \"rust\",\n    \"game\" # the game\n]\n";
+        assert_eq!(
+            added(commented, "rust"),
+            "[workspace]\nmembers = [\n    # \"rust\",\n    \"game\", # the game\n    \"rust\",\n]\n"
+        );
+    }
+
+    #[test]
+    fn workspace_member_trailing_comma() {
+        assert_eq!(added("[workspace]\nmembers = [\"game\",]\n", "rust"), "[workspace]\nmembers = [\"game\", \"rust\"]\n");
+        assert_eq!(
+            added("[workspace]\nmembers = [\n    \"game\",\n]\n", "rust"),
+            "[workspace]\nmembers = [\n    \"game\",\n    \"rust\",\n]\n"
+        );
+    }
+
+    #[test]
+    fn workspace_member_missing_members() {
+        assert_eq!(
+            added("[workspace]\nresolver = \"2\"\n", "rust"),
+            "[workspace]\nmembers = [\"rust\"]\nresolver = \"2\"\n"
+        );
+        assert_eq!(added("[workspace]", "rust"), "[workspace]\nmembers = [\"rust\"]\n");
+        assert_eq!(add_workspace_member("[package]\nname = \"rust\"\n", "rust"), WorkspaceMembership::Unchanged);
+    }
+
+    #[test]
+    fn workspace_member_other_occurrences() {
+        let manifest = "# \"rust\" is added by gen_gdext\n[workspace]\nmembers = [\"game\"]\nexclude = [\"rust\"]\n\n[workspace.dependencies]\nrust = { path = \"rust\" }\n";
+        assert_eq!(
+            added(manifest, "rust"),
+            "# \"rust\" is added by gen_gdext\n[workspace]\nmembers = [\"game\", \"rust\"]\nexclude = [\"rust\"]\n\n[workspace.dependencies]\nrust = { path = \"rust\" }\n"
+        );
+        assert_eq!(
+            add_workspace_member("[workspace]\nmembers = \"rust\"\n", "rust"),
+            WorkspaceMembership::Unchanged
+        );
+    }
 }